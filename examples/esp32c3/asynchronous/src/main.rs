@@ -9,6 +9,7 @@
 use defmt::info;
 use embassy_executor::Spawner;
 use embassy_time::{Duration, Timer};
+use embedded_tfluna::Startup;
 use embedded_tfluna::i2c::{Address, asynchronous::TFLuna};
 use esp_hal::clock::CpuClock;
 use esp_hal::timer::{OneShotTimer, systimer::SystemTimer, timg::TimerGroup};
@@ -55,13 +56,9 @@ async fn main(_spawner: Spawner) -> ! {
     let timer = OneShotTimer::new(timg0.timer0).into_async();
     let mut tfluna: TFLuna<_, _> = TFLuna::new(i2c, Address::default(), timer).unwrap();
 
-    // Restore factory defaults and then reboot device
-    tfluna.restore_factory_defaults().await.unwrap();
-    tfluna.reboot().await.unwrap();
-    Timer::after(Duration::from_millis(500)).await;
-
-    // Enable measurements
-    tfluna.enable().await.unwrap();
+    // Restore factory defaults, reboot device and enable measurements
+    // Use `Startup::SavedSettings` instead to keep the settings saved on the device
+    tfluna.init_default(Startup::FactoryDefaults).await.unwrap();
 
     loop {
         let measurement = tfluna.get_measurement().await.unwrap();
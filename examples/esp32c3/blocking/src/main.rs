@@ -7,6 +7,7 @@
 )]
 
 use defmt::info;
+use embedded_tfluna::Startup;
 use embedded_tfluna::i2c::{Address, TFLuna};
 use esp_hal::clock::CpuClock;
 use esp_hal::delay::Delay;
@@ -46,13 +47,9 @@ fn main() -> ! {
         .with_scl(scl_pin);
     let mut tfluna: TFLuna<_, _> = TFLuna::new(i2c, Address::default(), Delay::new()).unwrap();
 
-    // Restore factory defaults and then reboot device
-    tfluna.restore_factory_defaults().unwrap();
-    tfluna.reboot().unwrap();
-    Delay::new().delay_millis(500);
-
-    // Enable measurements
-    tfluna.enable().unwrap();
+    // Restore factory defaults, reboot device and enable measurements
+    // Use `Startup::SavedSettings` instead to keep the settings saved on the device
+    tfluna.start(Startup::FactoryDefaults).unwrap();
 
     loop {
         let measurement = tfluna.get_measurement().unwrap();
@@ -0,0 +1,65 @@
+#![no_std]
+#![no_main]
+#![deny(
+    clippy::mem_forget,
+    reason = "mem::forget is generally not safe to do with esp_hal types, especially those \
+    holding buffers for the duration of a data transfer."
+)]
+
+use defmt::{info, warn};
+use embedded_tfluna::i2c::{Address, TFLuna};
+use embedded_tfluna::{PowerMode, RangingMode, Startup};
+use esp_hal::clock::CpuClock;
+use esp_hal::delay::Delay;
+use esp_hal::main;
+use esp_hal::time::{Duration, Instant};
+use esp_hal::{
+    i2c::master::{Config as I2cConfig, I2c},
+    time::Rate,
+};
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+// This creates a default app-descriptor required by the esp-idf bootloader.
+// For more information see: <https://docs.espressif.com/projects/esp-idf/en/stable/esp32/api-reference/system/app_image_format.html#application-description>
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[main]
+fn main() -> ! {
+    rtt_target::rtt_init_defmt!();
+
+    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
+    let peripherals = esp_hal::init(config);
+
+    // I2C SDA (Data) Pin
+    let sda_pin = peripherals.GPIO8;
+    // I2C SCL (Clock) Pin
+    let scl_pin = peripherals.GPIO9;
+    let i2c_config = I2cConfig::default().with_frequency(Rate::from_khz(100));
+    let i2c = I2c::new(peripherals.I2C0, i2c_config)
+        .unwrap()
+        .with_sda(sda_pin)
+        .with_scl(scl_pin);
+    let mut tfluna: TFLuna<_, _> = TFLuna::new(i2c, Address::default(), Delay::new()).unwrap();
+
+    tfluna.start(Startup::FactoryDefaults).unwrap();
+    if !tfluna.ultra_low_power_reliable().unwrap() {
+        warn!("Ultra-low power mode is known to be unreliable with this firmware version");
+    }
+
+    // Only measure when triggered, then put the device in ultra-low power mode.
+    // This saves the settings and reboots the device.
+    tfluna.set_ranging_mode(RangingMode::Trigger).unwrap();
+    tfluna.set_power_mode(PowerMode::UltraLow).unwrap();
+
+    loop {
+        // Wake up, trigger, read and let the device go back to sleep
+        let measurement = tfluna.duty_cycle_read().unwrap();
+        info!("Distance = {:?}", measurement.distance);
+        let delay_start = Instant::now();
+        while delay_start.elapsed() < Duration::from_millis(5000) {}
+    }
+}
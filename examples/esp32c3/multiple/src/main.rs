@@ -0,0 +1,76 @@
+#![no_std]
+#![no_main]
+#![deny(
+    clippy::mem_forget,
+    reason = "mem::forget is generally not safe to do with esp_hal types, especially those \
+    holding buffers for the duration of a data transfer."
+)]
+
+use core::cell::RefCell;
+
+use defmt::info;
+use embedded_hal_bus::i2c::RefCellDevice;
+use embedded_tfluna::Startup;
+use embedded_tfluna::i2c::{Address, TFLuna};
+use esp_hal::clock::CpuClock;
+use esp_hal::delay::Delay;
+use esp_hal::main;
+use esp_hal::time::{Duration, Instant};
+use esp_hal::{
+    i2c::master::{Config as I2cConfig, I2c},
+    time::Rate,
+};
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+// This creates a default app-descriptor required by the esp-idf bootloader.
+// For more information see: <https://docs.espressif.com/projects/esp-idf/en/stable/esp32/api-reference/system/app_image_format.html#application-description>
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[main]
+fn main() -> ! {
+    // generator version: 0.5.0
+
+    rtt_target::rtt_init_defmt!();
+
+    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
+    let peripherals = esp_hal::init(config);
+
+    // I2C SDA (Data) Pin
+    let sda_pin = peripherals.GPIO8;
+    // I2C SCL (Clock) Pin
+    let scl_pin = peripherals.GPIO9;
+    let i2c_config = I2cConfig::default().with_frequency(Rate::from_khz(100));
+    let i2c = I2c::new(peripherals.I2C0, i2c_config)
+        .unwrap()
+        .with_sda(sda_pin)
+        .with_scl(scl_pin);
+
+    // Both sensors share the same bus, each one through its own `RefCellDevice`.
+    // Use `AtomicDevice` or `CriticalSectionDevice` instead if the bus is used from interrupts.
+    // The second sensor must have been re-addressed to 0x11 beforehand,
+    // e.g. with `set_slave_address` while it was alone on the bus.
+    let i2c = RefCell::new(i2c);
+    let mut front: TFLuna<_, _> =
+        TFLuna::new(RefCellDevice::new(&i2c), Address::default(), Delay::new()).unwrap();
+    let mut rear: TFLuna<_, _> =
+        TFLuna::new(RefCellDevice::new(&i2c), Address::from(0x11), Delay::new()).unwrap();
+
+    // Keep the settings saved on each device, in particular the slave address of the second one
+    front.start(Startup::SavedSettings).unwrap();
+    rear.start(Startup::SavedSettings).unwrap();
+
+    loop {
+        let front_measurement = front.get_measurement().unwrap();
+        let rear_measurement = rear.get_measurement().unwrap();
+        info!(
+            "Front distance = {:?}, Rear distance = {:?}",
+            front_measurement.distance, rear_measurement.distance
+        );
+        let delay_start = Instant::now();
+        while delay_start.elapsed() < Duration::from_millis(500) {}
+    }
+}
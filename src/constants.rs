@@ -0,0 +1,14 @@
+/// Signature returned by every TF-Luna device
+pub const SIGNATURE: [u8; 4] = *b"LUNA";
+
+/// Delay in milliseconds for the device to be ready again after a reboot
+#[cfg(any(feature = "async", feature = "i2c-blocking", feature = "uart"))]
+pub const REBOOT_DELAY_MS: u32 = 500;
+
+/// Framerates (in Hz) supported by the device, as listed in the manual, in ascending order.
+///
+/// The device measures at 500Hz internally and outputs every n-th measurement, hence 166Hz
+/// for 500Hz / 3. Other factors of 500Hz, e.g. 4Hz, are not listed and so not accepted.
+/// 500Hz is above the maximum output framerate of 250Hz.
+#[cfg(any(feature = "async", feature = "i2c-blocking", feature = "uart"))]
+pub const FRAMERATE_OPTIONS: [u16; 12] = [1, 2, 5, 10, 20, 25, 50, 100, 125, 166, 200, 250];
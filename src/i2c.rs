@@ -10,10 +10,43 @@
 //! | Master/Slave mode | Slave |
 //! | Default address | 0x10 |
 //! | Address range | 0x01~0x7F |
+//!
+//! # Interfaces
+//!
+//! The blocking interface is enabled by the `i2c-blocking` feature, which is on by default,
+//! and the asynchronous interface by the `async` feature. Projects that only use one of them
+//! can disable the other to reduce code size. [`TFLuna`] refers to the blocking driver,
+//! or to the asynchronous one when the blocking interface is disabled.
+//!
+//! # Sharing the device
+//!
+//! All methods take `&mut self`, so the asynchronous driver can be shared between tasks by
+//! putting it behind an async mutex, e.g. `embassy_sync::mutex::Mutex`, and calling methods
+//! on the guard. To keep other tasks from waiting, hold the guard only for the calls
+//! themselves and drop it before waiting for the next frame,
+//! see the `shared` package in `examples/esp32c3`.
+//!
+//! # Sharing the bus
+//!
+//! The driver takes any [`embedded_hal::i2c::I2c`] implementation, so several sensors at
+//! different addresses can share one bus through the wrappers of
+//! [`embedded-hal-bus`](https://crates.io/crates/embedded-hal-bus), e.g. `RefCellDevice`
+//! or `AtomicDevice`, with one wrapper per [`TFLuna`] instance,
+//! see the `multiple` package in `examples/esp32c3`.
 
-mod constants;
+pub(crate) mod constants;
 mod types;
 
+/// Log a state transition with `defmt::debug!` when the `log-transitions` feature is enabled,
+/// the arguments are not evaluated otherwise.
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+macro_rules! log_transition {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "log-transitions")]
+        defmt::debug!($($arg)*);
+    };
+}
+
 #[path = "i2c"]
 #[cfg(feature = "async")]
 pub mod asynchronous {
@@ -21,18 +54,33 @@ pub mod asynchronous {
     use bisync::asynchronous::*;
     #[allow(clippy::duplicate_mod)]
     mod device;
+    #[allow(clippy::duplicate_mod)]
+    mod proximity;
     pub use device::*;
+    pub use proximity::*;
 }
 
 #[path = "i2c"]
+#[cfg(feature = "i2c-blocking")]
 pub mod blocking {
     //! Blocking I2C interface
     use bisync::synchronous::*;
     #[allow(clippy::duplicate_mod)]
     mod device;
+    #[allow(clippy::duplicate_mod)]
+    mod proximity;
     pub use device::*;
+    pub use proximity::*;
 }
 
-pub use blocking::TFLuna;
-pub use constants::DEFAULT_SLAVE_ADDRESS;
-pub use types::{Address, Error};
+#[cfg(all(feature = "async", not(feature = "i2c-blocking")))]
+pub use asynchronous::{TFLuna, TFLunaBuilder, scan_addresses};
+#[cfg(feature = "i2c-blocking")]
+pub use blocking::{TFLuna, TFLunaBuilder, scan_addresses};
+pub use constants::{DEFAULT_SLAVE_ADDRESS, DEVICE_TICK_HZ};
+#[cfg(all(feature = "trace", any(feature = "async", feature = "i2c-blocking")))]
+pub use types::TraceEvent;
+pub use types::{
+    Address, AddressChangePending, AddressSet, Error, NoPin, PowerModeTiming, ProvisioningCheck,
+    ReadMode, RecoveryHint, RetryConfig,
+};
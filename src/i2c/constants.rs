@@ -1,26 +1,167 @@
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+use crate::types::FirmwareVersion;
+
 /// Default I2c Slave Address of the TF-Luna device
 pub const DEFAULT_SLAVE_ADDRESS: u8 = 0x10;
 
+/// Address returned by `Address::default()`
+///
+/// Set at compile time from the `EMBEDDED_TFLUNA_DEFAULT_ADDRESS` environment variable
+/// if present, e.g. `EMBEDDED_TFLUNA_DEFAULT_ADDRESS=0x20`, and [`DEFAULT_SLAVE_ADDRESS`] otherwise.
+pub const CONFIGURED_DEFAULT_SLAVE_ADDRESS: u8 =
+    match option_env!("EMBEDDED_TFLUNA_DEFAULT_ADDRESS") {
+        Some(value) => parse_slave_address(value),
+        None => DEFAULT_SLAVE_ADDRESS,
+    };
+
+/// Parse a decimal or `0x`-prefixed hexadecimal slave address at compile time.
+///
+/// Panics, i.e. fails the build, if the value is not a valid slave address.
+const fn parse_slave_address(value: &str) -> u8 {
+    let bytes = value.as_bytes();
+    let (mut index, radix) = if bytes.len() > 2 && bytes[0] == b'0' && (bytes[1] | 0x20) == b'x' {
+        (2, 16)
+    } else {
+        (0, 10)
+    };
+    if index == bytes.len() {
+        panic!("EMBEDDED_TFLUNA_DEFAULT_ADDRESS is empty");
+    }
+    let mut address: u32 = 0;
+    while index < bytes.len() {
+        let digit = match bytes[index] {
+            byte @ b'0'..=b'9' => byte - b'0',
+            byte @ b'a'..=b'f' => byte - b'a' + 10,
+            byte @ b'A'..=b'F' => byte - b'A' + 10,
+            _ => panic!("EMBEDDED_TFLUNA_DEFAULT_ADDRESS contains an invalid digit"),
+        } as u32;
+        if digit >= radix {
+            panic!("EMBEDDED_TFLUNA_DEFAULT_ADDRESS contains an invalid digit");
+        }
+        address = address * radix + digit;
+        if address > SLAVE_ADDRESS_MAXIMUM_VALUE as u32 {
+            panic!("EMBEDDED_TFLUNA_DEFAULT_ADDRESS is out of range");
+        }
+        index += 1;
+    }
+    if address < SLAVE_ADDRESS_MINIMUM_VALUE as u32 {
+        panic!("EMBEDDED_TFLUNA_DEFAULT_ADDRESS is out of range");
+    }
+    address as u8
+}
+
+/// Frequency in Hz of the device clock used for timestamps
+///
+/// The timestamp registers (0x06 and 0x07) count milliseconds since the device was powered on,
+/// as documented in the register table of the TF-Luna product manual.
+pub const DEVICE_TICK_HZ: u32 = 1000;
+
 // Command values
 /// Value to write for saving current settings
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
 pub const SAVE_COMMAND_VALUE: u8 = 1;
 /// Value to write for rebooting device
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
 pub const REBOOT_COMMAND_VALUE: u8 = 2;
 /// Value to write for restoring factory defaults
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
 pub const RESTORE_FACTORY_DEFAULTS_COMMAND_VALUE: u8 = 1;
 /// Value to write for triggering a measurement - Only useful when trigger ranging mode is selected
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
 pub const TRIGGER_COMMAND_VALUE: u8 = 1;
 /// Value to write for enabling device measurements
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
 pub const ENABLE_COMMAND_VALUE: u8 = 1;
 /// Value to write for disabling device measurements
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
 pub const DISABLE_COMMAND_VALUE: u8 = 0;
 /// Value to write for setting normal power mode
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
 pub const NORMAL_POWER_MODE_COMMAND_VALUE: u8 = 0;
 /// Value to write for setting power saving power mode
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
 pub const POWER_SAVING_POWER_MODE_COMMAND_VALUE: u8 = 1;
 /// Value to write for setting ultra-low power mode
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
 pub const ULTRA_LOWER_POWER_MODE_COMMAND_VALUE: u8 = 1;
 
 // Other values
+/// Minimum delay in milliseconds after waking up the device from ultra-low power mode, as per the manual
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const ULTRA_LOW_POWER_WAKE_UP_DELAY_MS: u32 = 12;
+/// Number of reads attempted by the recovery routine before giving up
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const RECOVERY_ATTEMPTS: u8 = 5;
+/// Delay in milliseconds between two reads of the recovery routine
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const RECOVERY_RETRY_DELAY_MS: u32 = 100;
+/// Number of polls for a fresh measurement after enabling the device before giving up
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const FRESH_MEASUREMENT_POLL_ATTEMPTS: u16 = 200;
+/// Delay in milliseconds between two polls for a fresh measurement
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const FRESH_MEASUREMENT_POLL_INTERVAL_MS: u32 = 10;
 pub const SLAVE_ADDRESS_MINIMUM_VALUE: u8 = 0x08;
 pub const SLAVE_ADDRESS_MAXIMUM_VALUE: u8 = 0x77;
+
+/// Rate in Hz at which the device measures internally, lower output framerates average measurements
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const INTERNAL_MEASUREMENT_RATE: u16 = 500;
+
+/// Delay in milliseconds for a power mode change to take effect
+pub const POWER_MODE_SWITCH_DELAY_MS: u32 = 100;
+/// Delay in milliseconds for the ultra-low power mode setting to be saved before rebooting
+pub const ULTRA_LOW_POWER_SAVE_DELAY_MS: u32 = 100;
+/// Delay in milliseconds for the device to be ready again after rebooting into or out of
+/// ultra-low power mode
+pub const ULTRA_LOW_POWER_REBOOT_DELAY_MS: u32 = 1000;
+
+/// Delay in milliseconds for a ranging mode change to take effect
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const RANGING_MODE_SWITCH_DELAY_MS: u32 = 100;
+
+/// Maximum number of samples taken by `get_robust_distance`
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const ROBUST_DISTANCE_MAX_SAMPLES: u8 = 16;
+/// Maximum number of samples taken by `get_median_distance`
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const MEDIAN_DISTANCE_MAX_SAMPLES: u8 = 9;
+
+/// Number of frame periods `get_measurement_fresh` waits for a new frame
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const FRESH_MEASUREMENT_MAX_FRAMES: u8 = 3;
+
+/// Maximum number of samples in the window of `wait_for_stable`
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const STABILITY_WINDOW_MAX_SAMPLES: u8 = 16;
+
+/// Distance in centimeters up to which the accuracy is fixed, as per the manual
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const FIXED_ACCURACY_MAXIMUM_DISTANCE: u16 = 300;
+
+/// Accuracy in centimeters for distances up to `FIXED_ACCURACY_MAXIMUM_DISTANCE`
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const FIXED_ACCURACY: u16 = 6;
+
+/// Accuracy in percent of the distance beyond `FIXED_ACCURACY_MAXIMUM_DISTANCE`
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const RELATIVE_ACCURACY_PERCENT: u16 = 2;
+
+/// Factory default framerate in Hz
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const DEFAULT_FRAMERATE: u16 = 100;
+
+/// Maximum framerate in Hz while the device is in power saving mode, as per the manual
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const POWER_SAVING_MAXIMUM_FRAMERATE: u16 = 10;
+
+/// Firmware versions on which ultra-low power mode is known to be unreliable
+///
+/// On these versions the device often fails to wake up or to return measurements
+/// after entering ultra-low power mode.
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub const ULTRA_LOW_POWER_UNRELIABLE_FIRMWARE_VERSIONS: [FirmwareVersion; 1] = [FirmwareVersion {
+    major: 3,
+    minor: 5,
+    revision: 1,
+}];
@@ -1,12 +1,23 @@
+use crate::constants::{FRAMERATE_OPTIONS, REBOOT_DELAY_MS, SIGNATURE};
 use crate::i2c::constants;
-use crate::i2c::types::{Address, Error, Register};
+use crate::i2c::types::{
+    Address, AddressChangePending, AddressSet, Error, NoPin, PowerModeTiming, ProvisioningCheck,
+    ReadMode, Register, RetryBudget, RetryConfig, TimestampTracker,
+};
+#[cfg(feature = "trace")]
+use crate::i2c::types::{TraceEvent, Tracer};
 
 use crate::types::{
-    FirmwareVersion, PowerMode, RangingMode, SensorReading, SerialNumber, Signature,
+    Config, ConfigWord, DistanceStatistics, FirmwareVersion, LossPolicy, MeasurementView,
+    PowerMode, Preset, RangingMode, SensorReading, SerialNumber, Signature, Startup, TimestampBase,
 };
 
 use super::{bisync, only_async, only_sync};
 
+use core::marker::PhantomData;
+
+use embedded_hal::digital::InputPin;
+
 #[only_sync]
 use embedded_hal::{
     delay::DelayNs,
@@ -71,13 +82,37 @@ use embedded_hal_async::{
 #[only_sync]
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct TFLuna<I2C: I2cTrait<SevenBitAddress>, D: DelayNs> {
+pub struct TFLuna<I2C: I2cTrait<SevenBitAddress>, D: DelayNs, P = NoPin> {
     /// Concrete I2C device implementation.
     i2c: I2C,
     /// I2C device address
     address: Address,
     /// Concrete delay implementation
     delay: D,
+    /// Optional callback invoked on every I2C transaction
+    #[cfg(feature = "trace")]
+    tracer: Option<Tracer>,
+    /// Set while a streamed measurement is being read, stays set if the read was cancelled
+    stream_read_interrupted: bool,
+    /// Timestamps of previous reads, used to tell new measurements apart
+    timestamps: TimestampTracker,
+    /// Last reliable distance read by `get_measurement_with_policy`
+    last_valid_distance: Option<u16>,
+    /// Retries applied to all operations
+    retry_config: RetryConfig,
+    /// How registers are read
+    read_mode: ReadMode,
+    /// Set once the device acknowledged a transaction at the current address
+    device_seen: bool,
+    /// Set by writes to persistent settings, cleared by saving the settings or rebooting
+    unsaved_changes: bool,
+    /// Power mode last set or read through the driver, `None` if unknown.
+    /// Reset to `None` when waking up from ultra-low power mode, until the device sleeps again.
+    power_mode: Option<PowerMode>,
+    /// Ranging mode last set or read through the driver, `None` if unknown
+    ranging_mode: Option<RangingMode>,
+    /// MCU input pin connected to the TF-Luna's pin 6, `NoPin` if not set
+    data_ready: P,
 }
 
 /// TF-Luna asynchronous controller/driver
@@ -130,13 +165,37 @@ pub struct TFLuna<I2C: I2cTrait<SevenBitAddress>, D: DelayNs> {
 #[only_async]
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct TFLuna<I2C: I2cTrait<SevenBitAddress>, D: DelayNs> {
+pub struct TFLuna<I2C: I2cTrait<SevenBitAddress>, D: DelayNs, P = NoPin> {
     /// Concrete I2C device implementation.
     i2c: I2C,
     /// I2C device address
     address: Address,
     /// Concrete delay implementation
     delay: D,
+    /// Optional callback invoked on every I2C transaction
+    #[cfg(feature = "trace")]
+    tracer: Option<Tracer>,
+    /// Set while a streamed measurement is being read, stays set if the read was cancelled
+    stream_read_interrupted: bool,
+    /// Timestamps of previous reads, used to tell new measurements apart
+    timestamps: TimestampTracker,
+    /// Last reliable distance read by `get_measurement_with_policy`
+    last_valid_distance: Option<u16>,
+    /// Retries applied to all operations
+    retry_config: RetryConfig,
+    /// How registers are read
+    read_mode: ReadMode,
+    /// Set once the device acknowledged a transaction at the current address
+    device_seen: bool,
+    /// Set by writes to persistent settings, cleared by saving the settings or rebooting
+    unsaved_changes: bool,
+    /// Power mode last set or read through the driver, `None` if unknown.
+    /// Reset to `None` when waking up from ultra-low power mode, until the device sleeps again.
+    power_mode: Option<PowerMode>,
+    /// Ranging mode last set or read through the driver, `None` if unknown
+    ranging_mode: Option<RangingMode>,
+    /// MCU input pin connected to the TF-Luna's pin 6, `NoPin` if not set
+    data_ready: P,
 }
 
 impl<I2C, D> TFLuna<I2C, D>
@@ -145,15 +204,208 @@ where
     D: DelayNs,
 {
     /// Associated method to create a new instance of the controller
+    ///
+    /// # Notes
+    /// * No data is sent to the device, in particular its saved settings are left untouched.
+    ///   Use [`TFLuna::start()`] to choose between keeping them and restoring factory defaults.
     pub fn new(i2c: I2C, address: Address, delay: D) -> Result<Self, Error<I2C::Error>> {
         let sensor = Self {
             i2c,
             address,
             delay,
+            #[cfg(feature = "trace")]
+            tracer: None,
+            stream_read_interrupted: false,
+            timestamps: TimestampTracker::default(),
+            last_valid_distance: None,
+            retry_config: RetryConfig::default(),
+            read_mode: ReadMode::default(),
+            device_seen: false,
+            unsaved_changes: false,
+            power_mode: None,
+            ranging_mode: None,
+            data_ready: NoPin,
         };
         Ok(sensor)
     }
 
+    /// Create a new instance of the controller and check that a TF-Luna answers at the address.
+    ///
+    /// # Returns
+    /// * `Ok(Self)`: if the device answered with the 'L' 'U' 'N' 'A' signature.
+    /// * `Err(Error::NotFound)`: if nothing answered at the address or the signature didn't match.
+    /// * `Err(Error::I2c(I2CError))`: if there was another I2C error.
+    ///
+    /// # Notes
+    /// * The bus and delay are dropped on failure. Use [`TFLuna::new()`] to construct the
+    ///   instance before the bus is ready, and [`TFLuna::ping()`] to check it later on.
+    /// * No data is written to the device, its saved settings are left untouched.
+
+    #[bisync]
+    pub async fn new_probed(
+        i2c: I2C,
+        address: Address,
+        delay: D,
+    ) -> Result<Self, Error<I2C::Error>> {
+        let mut sensor = Self::new(i2c, address, delay)?;
+        match sensor.get_signature().await {
+            Ok(signature) if signature.is_valid() => Ok(sensor),
+            Ok(_) => Err(Error::NotFound),
+            Err(e) if Self::is_nack(&e) => Err(Error::NotFound),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Create a builder that configures the device when constructing the controller.
+    ///
+    /// See [`TFLunaBuilder`].
+    pub fn builder() -> TFLunaBuilder<I2C, D> {
+        TFLunaBuilder::new()
+    }
+}
+
+impl<I2C, D, P> TFLuna<I2C, D, P>
+where
+    I2C: I2cTrait<SevenBitAddress>,
+    D: DelayNs,
+{
+    /// Set the retries applied to all operations.
+    ///
+    /// # Arguments
+    /// * `retry_config`: number of retries and delays between them, see [`RetryConfig`].
+    ///
+    /// # Notes
+    /// * By default, nothing is retried.
+    /// * Reads and writes that are not acknowledged (NACK) or that lost arbitration
+    ///   to another master on a multi-master bus are retried as configured.
+    ///   Other bus errors are never retried.
+    /// * Registers holding an enumerated value (ranging mode, power mode) are read again
+    ///   as configured when they contain an invalid value.
+    /// * [`TFLuna::get_power_mode()`] and [`TFLuna::wake_from_ultra_low_power()`] don't retry NACKs,
+    ///   since they rely on a NACK meaning the device is in ultra-low power mode.
+    pub fn with_retries(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// Set how registers are read.
+    ///
+    /// # Arguments
+    /// * `read_mode`: whether the register address and the data are transferred in a single
+    ///   transaction or in two, see [`ReadMode`].
+    ///
+    /// # Notes
+    /// * By default, a single transaction with a repeated start is used.
+    /// * Some USB-I2C bridges don't handle repeated starts, use [`ReadMode::StopStart`] for them.
+    pub fn with_read_mode(mut self, read_mode: ReadMode) -> Self {
+        self.read_mode = read_mode;
+        self
+    }
+
+    /// Replace the delay implementation.
+    ///
+    /// # Arguments
+    /// * `delay`: new delay implementation, used by all later operations.
+    ///
+    /// # Returns
+    /// * `TFLuna<I2C, D2, P>`: the same controller, with its state preserved.
+    ///
+    /// # Notes
+    /// * No data is sent to the device.
+    /// * The previous delay is dropped, use [`TFLuna::release()`] instead to get it back.
+    /// * Useful when the controller is created before a hardware timer is available.
+    pub fn with_delay<D2: DelayNs>(self, delay: D2) -> TFLuna<I2C, D2, P> {
+        TFLuna {
+            i2c: self.i2c,
+            address: self.address,
+            delay,
+            #[cfg(feature = "trace")]
+            tracer: self.tracer,
+            stream_read_interrupted: self.stream_read_interrupted,
+            timestamps: self.timestamps,
+            last_valid_distance: self.last_valid_distance,
+            retry_config: self.retry_config,
+            read_mode: self.read_mode,
+            device_seen: self.device_seen,
+            unsaved_changes: self.unsaved_changes,
+            power_mode: self.power_mode,
+            ranging_mode: self.ranging_mode,
+            data_ready: self.data_ready,
+        }
+    }
+
+    /// Set the pin used to know when a new measurement is available.
+    ///
+    /// # Arguments
+    /// * `pin`: MCU input pin connected to the TF-Luna's pin 6 (multiplexing output),
+    ///   replacing the previous one if any.
+    ///
+    /// # Returns
+    /// * `TFLuna<I2C, D, P2>`: the same controller, with its state preserved.
+    ///
+    /// # Notes
+    /// * No data is sent to the device.
+    /// * The pin is used by [`TFLuna::measure_if_ready()`], see [`TFLuna::is_data_ready()`]
+    ///   for the behavior of pin 6.
+    pub fn with_data_ready<P2: InputPin>(self, pin: P2) -> TFLuna<I2C, D, P2> {
+        TFLuna {
+            i2c: self.i2c,
+            address: self.address,
+            delay: self.delay,
+            #[cfg(feature = "trace")]
+            tracer: self.tracer,
+            stream_read_interrupted: self.stream_read_interrupted,
+            timestamps: self.timestamps,
+            last_valid_distance: self.last_valid_distance,
+            retry_config: self.retry_config,
+            read_mode: self.read_mode,
+            device_seen: self.device_seen,
+            unsaved_changes: self.unsaved_changes,
+            power_mode: self.power_mode,
+            ranging_mode: self.ranging_mode,
+            data_ready: pin,
+        }
+    }
+
+    /// Set a callback that gets invoked on every I2C transaction issued by the driver.
+    ///
+    /// # Arguments
+    /// * `tracer`: function called with a [`TraceEvent`] describing the transaction.
+    ///
+    /// # Notes
+    /// * Writes are traced before being sent to the device, so failed writes are traced as well.
+    /// * Reads are traced only once they succeed, with the bytes returned by the device.
+    #[cfg(feature = "trace")]
+    pub fn set_trace(&mut self, tracer: fn(&TraceEvent)) {
+        self.tracer = Some(Tracer(tracer));
+    }
+
+    /// Remove the previously set trace callback, if any.
+    #[cfg(feature = "trace")]
+    pub fn clear_trace(&mut self) {
+        self.tracer = None;
+    }
+
+    /// Consume the controller and return the I2C bus and delay.
+    ///
+    /// # Notes
+    /// * No data is sent to the device, it keeps its current state.
+    /// * Useful to reuse the bus for another device, or to create a new instance
+    ///   at another address.
+    /// * The data-ready pin, if any, is dropped, use [`TFLuna::release_with_pin()`] to get it back.
+    pub fn release(self) -> (I2C, D) {
+        (self.i2c, self.delay)
+    }
+
+    /// Consume the controller and return the I2C bus, delay and data-ready pin.
+    ///
+    /// # Notes
+    /// * Same as [`TFLuna::release()`], the pin is [`NoPin`] if none was set with
+    ///   [`TFLuna::with_data_ready()`].
+    pub fn release_with_pin(self) -> (I2C, D, P) {
+        (self.i2c, self.delay, self.data_ready)
+    }
+
     /// Combine two bytes from a buffer into a 16-bit word (little-endian).
     ///
     /// # Arguments
@@ -165,22 +417,89 @@ where
         buffer[0] as u16 + ((buffer[1] as u16) << 8)
     }
 
+    /// Check whether an error is a NACK that should be retried.
+    fn is_nack(error: &Error<I2C::Error>) -> bool {
+        matches!(error, Error::I2c(e) if matches!(e.kind(), ErrorKind::NoAcknowledge(_)))
+    }
+
+    /// Read registers, retrying NACKs and arbitration losses as configured.
     #[bisync]
     async fn read<const N: usize>(
         &mut self,
-        register: Register,
+        register: u8,
         buffer: &mut [u8; N],
     ) -> Result<(), Error<I2C::Error>> {
-        self.i2c
-            .write_read(self.address.into(), &[register as u8], buffer)
-            .await
-            .map_err(Error::I2c)?;
+        let mut retries = RetryBudget::new(&self.retry_config);
+        loop {
+            match self.read_once(register, buffer).await {
+                Err(Error::I2c(e)) => match retries.next_delay_ms(&self.retry_config, e.kind()) {
+                    Some(delay_ms) => self.delay.delay_ms(delay_ms).await,
+                    None => return Err(Error::I2c(e)),
+                },
+                result => return result,
+            }
+        }
+    }
+
+    /// Read registers in a single attempt.
+    #[bisync]
+    async fn read_once<const N: usize>(
+        &mut self,
+        register: u8,
+        buffer: &mut [u8; N],
+    ) -> Result<(), Error<I2C::Error>> {
+        match self.read_mode {
+            ReadMode::RepeatedStart => {
+                self.i2c
+                    .write_read(self.address.into(), &[register], buffer)
+                    .await?
+            }
+            ReadMode::StopStart => {
+                self.i2c.write(self.address.into(), &[register]).await?;
+                self.i2c.read(self.address.into(), buffer).await?;
+            }
+        }
+        self.device_seen = true;
+        #[cfg(feature = "trace")]
+        if let Some(Tracer(tracer)) = self.tracer {
+            tracer(&TraceEvent::Read {
+                register,
+                data: buffer,
+            });
+        }
         Ok(())
     }
 
+    /// Write registers, retrying NACKs and arbitration losses as configured.
     #[bisync]
     async fn write<const N: usize>(&mut self, buffer: &[u8; N]) -> Result<(), Error<I2C::Error>> {
+        let mut retries = RetryBudget::new(&self.retry_config);
+        loop {
+            match self.write_once(buffer).await {
+                Err(Error::I2c(e)) => match retries.next_delay_ms(&self.retry_config, e.kind()) {
+                    Some(delay_ms) => self.delay.delay_ms(delay_ms).await,
+                    None => return Err(Error::I2c(e)),
+                },
+                result => return result,
+            }
+        }
+    }
+
+    /// Write registers in a single attempt.
+    #[bisync]
+    async fn write_once<const N: usize>(
+        &mut self,
+        buffer: &[u8; N],
+    ) -> Result<(), Error<I2C::Error>> {
+        #[cfg(feature = "trace")]
+        if let (Some(Tracer(tracer)), [register, data @ ..]) = (self.tracer, buffer.as_slice()) {
+            tracer(&TraceEvent::Write {
+                register: *register,
+                data,
+            });
+        }
         self.i2c.write(self.address.into(), buffer).await?;
+        self.device_seen = true;
         Ok(())
     }
 
@@ -191,17 +510,68 @@ where
         register: Register,
         content: u8,
     ) -> Result<(), Error<I2C::Error>> {
-        self.write(&[register as u8, content]).await
+        self.check_awake(register)?;
+        self.write(&[register as u8, content]).await?;
+        self.track_unsaved_changes(register);
+        Ok(())
+    }
+
+    /// Refuse writes to persistent settings while the device is in ultra-low power mode,
+    /// where it wouldn't acknowledge them.
+    fn check_awake(&self, register: Register) -> Result<(), Error<I2C::Error>> {
+        if self.power_mode == Some(PowerMode::UltraLow) && register.is_persistent_setting() {
+            return Err(Error::UltraLowPowerActive);
+        }
+        Ok(())
+    }
+
+    /// Record writes to persistent settings.
+    fn track_unsaved_changes(&mut self, register: Register) {
+        if register.is_persistent_setting() {
+            self.unsaved_changes = true;
+        }
     }
 
     /// Read the contents of a single register
     #[bisync]
     async fn read_byte(&mut self, register: Register) -> Result<u8, Error<I2C::Error>> {
         let mut buffer = [0; 1];
-        self.read(register, &mut buffer).await?;
+        self.read(register as u8, &mut buffer).await?;
         Ok(buffer[0])
     }
 
+    /// Read a single register holding one of the values `0..=max`.
+    ///
+    /// Invalid values are read again as configured by the retry policy.
+    /// NACKs are only retried if `retry_nack` is set.
+    #[bisync]
+    async fn read_enumerated_byte(
+        &mut self,
+        register: Register,
+        max: u8,
+        retry_nack: bool,
+    ) -> Result<u8, Error<I2C::Error>> {
+        let mut retries_left = self.retry_config.invalid_data_retries;
+        loop {
+            let mut buffer = [0; 1];
+            if retry_nack {
+                self.read(register as u8, &mut buffer).await?;
+            } else {
+                self.read_once(register as u8, &mut buffer).await?;
+            }
+            match buffer[0] {
+                value if value <= max => return Ok(value),
+                _ if retries_left > 0 => {
+                    retries_left -= 1;
+                    self.delay
+                        .delay_ms(self.retry_config.invalid_data_delay_ms)
+                        .await;
+                }
+                value => return Err(Error::InvalidData(value)),
+            }
+        }
+    }
+
     /// Read word (two bytes) from two consecutive registers
     ///
     /// # Arguments
@@ -213,7 +583,7 @@ where
     #[bisync]
     async fn read_word(&mut self, register: Register) -> Result<u16, Error<I2C::Error>> {
         let mut buffer = [0; 2];
-        self.read(register, &mut buffer).await?;
+        self.read(register as u8, &mut buffer).await?;
         Ok(self.combine_buffer_into_word(&buffer))
     }
 
@@ -238,7 +608,10 @@ where
     ) -> Result<(), Error<I2C::Error>> {
         let low_byte = (value & 0xFF) as u8;
         let high_byte = ((value >> 8) & 0xFF) as u8;
-        self.write(&[register as u8, low_byte, high_byte]).await
+        self.check_awake(register)?;
+        self.write(&[register as u8, low_byte, high_byte]).await?;
+        self.track_unsaved_changes(register);
+        Ok(())
     }
 
     /// Restore all settings to factory defaults.
@@ -251,25 +624,176 @@ where
             Register::RestoreFactoryDefaults,
             constants::RESTORE_FACTORY_DEFAULTS_COMMAND_VALUE,
         )
-        .await
+        .await?;
+        self.ranging_mode = None;
+        self.power_mode = None;
+        Ok(())
+    }
+
+    /// Start the device.
+    ///
+    /// # Arguments
+    /// * `startup`: whether to keep the saved settings or to restore factory defaults first.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the device was started successfully.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * With [`Startup::FactoryDefaults`], factory defaults are restored and the device
+    ///   is rebooted, after which the method waits 500ms for the device to be ready again.
+    /// * With [`Startup::SavedSettings`], measurements are enabled without touching the settings.
+    ///   The getters (e.g. [`TFLuna::get_framerate()`]) can then be used to confirm them.
+
+    #[bisync]
+    pub async fn start(&mut self, startup: Startup) -> Result<(), Error<I2C::Error>> {
+        if startup == Startup::FactoryDefaults {
+            self.restore_factory_defaults().await?;
+            self.reboot().await?;
+            // Wait for the device to be ready again
+            self.delay.delay_ms(REBOOT_DELAY_MS).await;
+        }
+        self.enable().await
+    }
+
+    /// Restore factory defaults, reboot the device, wait for it to be ready and enable measurements.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the device was reset and enabled successfully.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The device is given 500ms to reboot, using the delay passed to [`TFLuna::new()`].
+    /// * Same as [`TFLuna::start()`] with [`Startup::FactoryDefaults`].
+    #[bisync]
+    pub async fn reset_and_provision(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.start(Startup::FactoryDefaults).await
+    }
+
+    /// Initialize the device and wait until it produces fresh measurements.
+    ///
+    /// # Arguments
+    /// * `startup`: whether to keep the saved settings or to restore factory defaults first.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: first measurement taken after the device was enabled.
+    /// * `Err(Error::Timeout)`: if the device did not answer after the reboot
+    ///   or did not produce a new measurement in time.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * With [`Startup::FactoryDefaults`], factory defaults are restored and the device is rebooted.
+    ///   Instead of waiting a fixed time, the device is then polled until it answers again,
+    ///   in the same way as [`TFLuna::recover()`].
+    /// * After enabling measurements, the measurement registers are polled every 10ms
+    ///   until the timestamp changes, for at most 2s. This covers framerates down to 1Hz.
+    /// * With [`Startup::SavedSettings`], the ranging mode is read first. If the saved settings
+    ///   use [`RangingMode::Trigger`], no new measurement would arrive on its own, so one is
+    ///   triggered and read after one frame period instead, see [`TFLuna::trigger_and_measure()`].
+    /// * This is the counterpart of [`TFLuna::start_continuous()`] that keeps the configured
+    ///   framerate and ranging mode.
+
+    #[bisync]
+    pub async fn init_default(
+        &mut self,
+        startup: Startup,
+    ) -> Result<SensorReading, Error<I2C::Error>> {
+        if startup == Startup::FactoryDefaults {
+            self.restore_factory_defaults().await?;
+            self.reboot().await?;
+            self.wait_until_responsive().await?;
+        }
+        self.enable().await?;
+        if startup == Startup::SavedSettings
+            && self.get_ranging_mode().await? == RangingMode::Trigger
+        {
+            let settle_ms = self.trigger_settle_ms().await?;
+            self.trigger_measurement().await?;
+            self.delay.delay_ms(settle_ms).await;
+            return self.read_triggered().await;
+        }
+        let first = self.get_measurement().await?;
+        for _ in 0..constants::FRESH_MEASUREMENT_POLL_ATTEMPTS {
+            self.delay
+                .delay_ms(constants::FRESH_MEASUREMENT_POLL_INTERVAL_MS)
+                .await;
+            let measurement = self.get_measurement().await?;
+            if measurement.timestamp != first.timestamp {
+                return Ok(measurement);
+            }
+        }
+        Err(Error::Timeout)
     }
 
     /// Save current settings to persistent storage.
     #[bisync]
     pub async fn save_settings(&mut self) -> Result<(), Error<I2C::Error>> {
         self.write_byte(Register::Save, constants::SAVE_COMMAND_VALUE)
-            .await
+            .await?;
+        self.unsaved_changes = false;
+        Ok(())
+    }
+
+    /// Check whether settings were changed since they were last saved.
+    ///
+    /// # Returns
+    /// * `true`: if a setting was written since the last call to [`TFLuna::save_settings()`]
+    ///   or [`TFLuna::reboot()`], i.e. it would be lost on reboot.
+    /// * `false`: otherwise.
+    ///
+    /// # Notes
+    /// * This is bookkeeping done by the driver without any I2C traffic, changes made
+    ///   before the driver was created or by another driver instance are not known.
+    /// * Settings are the slave address, ranging mode, framerate, power saving mode,
+    ///   signal strength threshold, dummy distance, minimum and maximum distance.
+    ///   Enabling or disabling measurements is not considered a setting change.
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved_changes
     }
 
     /// Set enable bit.
     ///
     /// Calling this method will enable the device's measurements.
+    ///
+    /// # Notes
+    /// * Measurements resume at the configured framerate, the first new measurement
+    ///   is available after one frame period, i.e. `1000 / framerate` milliseconds.
+    /// * For duty-cycled operation, [`TFLuna::set_idle()`] avoids having to re-enable the device.
     #[bisync]
     pub async fn enable(&mut self) -> Result<(), Error<I2C::Error>> {
         self.write_byte(Register::Enable, constants::ENABLE_COMMAND_VALUE)
             .await
     }
 
+    /// Start continuous measurements at the given framerate.
+    ///
+    /// Sets [`RangingMode::Continuous`], sets the framerate and enables the device,
+    /// then waits for one frame period before reading the first measurement.
+    ///
+    /// # Arguments
+    /// * `framerate`: desired framerate in Hz, see [`TFLuna::framerate_options()`].
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: first measurement taken at the new framerate.
+    /// * `Err(Error::InvalidParameter)`: if framerate is invalid, nothing is sent to the device.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+
+    #[bisync]
+    pub async fn start_continuous(
+        &mut self,
+        framerate: u16,
+    ) -> Result<SensorReading, Error<I2C::Error>> {
+        if !FRAMERATE_OPTIONS.contains(&framerate) {
+            return Err(Error::InvalidParameter);
+        }
+        self.set_ranging_mode(RangingMode::Continuous).await?;
+        self.set_framerate(framerate).await?;
+        self.enable().await?;
+        // Wait for a full frame at the new framerate
+        self.delay.delay_ms(1000 / framerate as u32).await;
+        self.get_measurement().await
+    }
+
     /// Unset enable bit
     ///
     /// Calling this method will disable the device's measurements.
@@ -279,11 +803,66 @@ where
             .await
     }
 
+    /// Check whether the device's measurements are enabled.
+    ///
+    /// # Returns
+    /// * `Ok(bool)`: `true` if measurements are enabled, `false` if they are disabled.
+    /// * `Err(Error::InvalidData)`: if register contains invalid value.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    #[bisync]
+    pub async fn is_enabled(&mut self) -> Result<bool, Error<I2C::Error>> {
+        match self.read_byte(Register::Enable).await? {
+            constants::ENABLE_COMMAND_VALUE => Ok(true),
+            constants::DISABLE_COMMAND_VALUE => Ok(false),
+            value => Err(Error::InvalidData(value)),
+        }
+    }
+
     /// Reboots device
     #[bisync]
     pub async fn reboot(&mut self) -> Result<(), Error<I2C::Error>> {
         self.write_byte(Register::ShutdownReboot, constants::REBOOT_COMMAND_VALUE)
-            .await
+            .await?;
+        // Unsaved settings are lost on reboot
+        self.unsaved_changes = false;
+        self.ranging_mode = None;
+        self.power_mode = None;
+        Ok(())
+    }
+
+    /// Attempt to bring an unresponsive device back.
+    ///
+    /// Sends a reboot command and then performs a bounded number of single byte reads
+    /// until the device answers.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the device answered one of the reads.
+    /// * `Err(Error::Timeout)`: if the device stayed unresponsive.
+    ///
+    /// # Notes
+    /// * Failures of the reboot command are ignored since a wedged device may not acknowledge it.
+    /// * This is only a driver-level first attempt. If the device holds SDA low,
+    ///   the bus has to be recovered by the caller (e.g. by clocking SCL manually)
+    ///   before calling this method.
+
+    #[bisync]
+    pub async fn recover(&mut self) -> Result<(), Error<I2C::Error>> {
+        let _ = self.reboot().await;
+        self.wait_until_responsive().await
+    }
+
+    /// Wait for the device to answer after a reboot.
+    #[bisync]
+    async fn wait_until_responsive(&mut self) -> Result<(), Error<I2C::Error>> {
+        for _ in 0..constants::RECOVERY_ATTEMPTS {
+            self.delay
+                .delay_ms(constants::RECOVERY_RETRY_DELAY_MS)
+                .await;
+            if self.read_byte(Register::Distance).await.is_ok() {
+                return Ok(());
+            }
+        }
+        Err(Error::Timeout)
     }
 
     /// Get the device firmware.
@@ -291,10 +870,15 @@ where
     /// # Returns
     /// * `Ok(FirmwareVersion)`: current firmware version.
     /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Measurements don't need to be enabled, so the firmware can be checked, e.g. with
+    ///   [`TFLuna::require_firmware()`], before deciding to call [`TFLuna::enable()`].
     #[bisync]
     pub async fn get_firmware_version(&mut self) -> Result<FirmwareVersion, Error<I2C::Error>> {
         let mut buffer = [0; 3];
-        self.read(Register::FirmwareVersion, &mut buffer).await?;
+        self.read(Register::FirmwareVersion as u8, &mut buffer)
+            .await?;
         let version = FirmwareVersion {
             major: buffer[2],
             minor: buffer[1],
@@ -303,15 +887,43 @@ where
         Ok(version)
     }
 
+    /// Check that the device firmware is at least the given version.
+    ///
+    /// # Arguments
+    /// * `min`: minimum required firmware version.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the firmware version is `min` or newer.
+    /// * `Err(Error::UnsupportedFirmware { found, required })`: if the firmware is older than `min`.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    #[bisync]
+    pub async fn require_firmware(
+        &mut self,
+        min: FirmwareVersion,
+    ) -> Result<(), Error<I2C::Error>> {
+        let found = self.get_firmware_version().await?;
+        if found < min {
+            return Err(Error::UnsupportedFirmware {
+                found,
+                required: min,
+            });
+        }
+        Ok(())
+    }
+
     /// Get the device's serial number.
     ///
     /// # Returns
     /// * `Ok(SerialNumber)`: device serial number.
     /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Also available while measurements are disabled, e.g. to identify a device
+    ///   during provisioning without starting measurements.
     #[bisync]
     pub async fn get_serial_number(&mut self) -> Result<SerialNumber, Error<I2C::Error>> {
         let mut buffer = [0; 14];
-        self.read(Register::SerialNumber, &mut buffer).await?;
+        self.read(Register::SerialNumber as u8, &mut buffer).await?;
         Ok(SerialNumber(buffer))
     }
 
@@ -320,71 +932,217 @@ where
     /// # Returns
     /// * `Ok(Signature)`: 4-byte ASCII signature.
     /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Reading the signature doesn't enable measurements, so it can be used to probe
+    ///   a device before calling [`TFLuna::enable()`].
     #[bisync]
     pub async fn get_signature(&mut self) -> Result<Signature, Error<I2C::Error>> {
         let mut buffer = [0; 4];
-        self.read::<4>(Register::Signature, &mut buffer).await?;
+        self.read::<4>(Register::Signature as u8, &mut buffer)
+            .await?;
         Ok(Signature(buffer))
     }
 
-    /// Get the current I2C slave address of the device.
+    /// Check that the device is a TF-Luna by reading its signature.
     ///
     /// # Returns
-    /// * `Ok(u8)`: current slave address.
+    /// * `Ok(())`: if the signature is 'L' 'U' 'N' 'A'.
+    /// * `Err(Error::InvalidData)`: with the first unexpected byte if the signature doesn't match.
     /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
     #[bisync]
-    pub async fn get_slave_address(&mut self) -> Result<u8, Error<I2C::Error>> {
-        self.read_byte(Register::SlaveAddress).await
+    pub async fn verify_signature(&mut self) -> Result<(), Error<I2C::Error>> {
+        let signature = self.get_signature().await?;
+        match signature
+            .0
+            .iter()
+            .zip(SIGNATURE)
+            .find(|&(&byte, expected)| byte != expected)
+        {
+            Some((&byte, _)) => Err(Error::InvalidData(byte)),
+            None => Ok(()),
+        }
     }
 
-    /// Set the I2C slave address of the device.
-    ///
-    /// # Arguments
-    /// * `address`: New slave address.
+    /// Check that the device answers, with a single byte read.
     ///
     /// # Returns
-    /// * `Ok(())`: if address was set successfully.
-    /// * `Err(Error::InvalidParameter)`: if address is out of valid range.
-    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    /// * `Ok(())`: if the device acknowledged the read.
+    /// * `Err(Error::NotFound)`: if the read was not acknowledged.
+    /// * `Err(Error::I2c(I2CError))`: if there was another I2C error.
     ///
     /// # Notes
-    /// * Valid addresses are in the range [0x08, 0x77]
-    /// * If you change the I2C slave address you will have to recreate an instance of [`TFLuna`]
-    ///   with the new address.
-
+    /// * This reads the distance low byte register and is meant as a cheap presence check,
+    ///   use [`TFLuna::get_signature()`] to make sure the device is a TF-Luna.
+    /// * A device in ultra-low power mode doesn't acknowledge the read either,
+    ///   which wakes it up.
     #[bisync]
-    pub async fn set_slave_address(&mut self, address: u8) -> Result<(), Error<I2C::Error>> {
-        if !(constants::SLAVE_ADDRESS_MINIMUM_VALUE..=constants::SLAVE_ADDRESS_MAXIMUM_VALUE)
-            .contains(&address)
-        {
-            return Err(Error::InvalidParameter);
+    pub async fn ping(&mut self) -> Result<(), Error<I2C::Error>> {
+        match self.read_byte(Register::Distance).await {
+            Ok(_) => Ok(()),
+            Err(e) if Self::is_nack(&e) => Err(Error::NotFound),
+            Err(e) => Err(e),
         }
-        self.write_byte(Register::SlaveAddress, address).await
     }
 
-    /// Get the current power mode of the device.
+    /// Describe the device identity in a single line, e.g. `TF-Luna SN=T3300245010082 FW=3.5.1`.
     ///
     /// # Returns
-    /// * `Ok(PowerMode)`: current power mode.
-    /// * `Err(Error::InvalidState)`: if registers contain invalid values,
+    /// * `Ok(String)`: device description.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
     ///
     /// # Notes
-    /// Reading registers will wake up the device from ultra-low power mode.
-    /// Avoid frequent calls when ultra-low power mode is expected.
-
+    /// * Reads the serial number and the firmware version. Non-printable serial number
+    ///   bytes are replaced with `?`.
+    #[cfg(feature = "alloc")]
     #[bisync]
-    pub async fn get_power_mode(&mut self) -> Result<PowerMode, Error<I2C::Error>> {
-        let power_saving_mode_value = self.read_byte(Register::PowerSavingMode).await;
+    pub async fn describe(&mut self) -> Result<alloc::string::String, Error<I2C::Error>> {
+        let serial_number = self.get_serial_number().await?;
+        let firmware_version = self.get_firmware_version().await?;
+        Ok(alloc::format!(
+            "TF-Luna SN={} FW={}",
+            serial_number,
+            firmware_version
+        ))
+    }
 
-        match power_saving_mode_value {
-            Ok(0x00) => Ok(PowerMode::Normal),
-            Ok(0x01) => Ok(PowerMode::PowerSaving),
-            Ok(val) => Err(Error::InvalidData(val)),
+    /// Get the current I2C slave address of the device.
+    ///
+    /// # Returns
+    /// * `Ok(u8)`: current slave address.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    #[bisync]
+    pub async fn get_slave_address(&mut self) -> Result<u8, Error<I2C::Error>> {
+        self.read_byte(Register::SlaveAddress).await
+    }
+
+    /// Check that the device's slave address register matches the address used by this instance.
+    ///
+    /// # Returns
+    /// * `Ok(true)`: if the slave address register holds the address of this instance.
+    /// * `Ok(false)`: if it holds another address, e.g. a change that wasn't committed yet.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * A new address written with [`TFLuna::set_slave_address()`] is already in the register
+    ///   while the device still answers at the old one, until it is saved and the device rebooted.
+
+    #[bisync]
+    pub async fn verify_address(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let address = self.get_slave_address().await?;
+        Ok(address == u8::from(self.address))
+    }
+
+    /// Set the I2C slave address of the device.
+    ///
+    /// # Arguments
+    /// * `address`: New slave address.
+    ///
+    /// # Returns
+    /// * `Ok(AddressChangePending)`: if address was written successfully.
+    /// * `Err(Error::InvalidParameter)`: if address is out of valid range.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Valid addresses are in the range [0x08, 0x77]
+    /// * The new address only takes effect after saving the settings and rebooting the device.
+    ///   Pass the returned [`AddressChangePending`] to [`TFLuna::commit_address_change()`]
+    ///   to do that and to switch this instance to the new address.
+
+    #[bisync]
+    pub async fn set_slave_address(
+        &mut self,
+        address: u8,
+    ) -> Result<AddressChangePending, Error<I2C::Error>> {
+        self.set_slave_address_raw(address).await?;
+        Ok(AddressChangePending {
+            address: Address::from(address),
+        })
+    }
+
+    /// Set the I2C slave address of the device without any follow-up.
+    ///
+    /// # Arguments
+    /// * `address`: New slave address.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if address was set successfully.
+    /// * `Err(Error::InvalidParameter)`: if address is out of valid range.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Valid addresses are in the range [0x08, 0x77]
+    /// * The new address only takes effect after saving the settings and rebooting the device.
+    ///   After that you will have to recreate an instance of [`TFLuna`] with the new address.
+    /// * Prefer [`TFLuna::set_slave_address()`] unless you need to control these steps yourself.
+
+    #[bisync]
+    pub async fn set_slave_address_raw(&mut self, address: u8) -> Result<(), Error<I2C::Error>> {
+        if !(constants::SLAVE_ADDRESS_MINIMUM_VALUE..=constants::SLAVE_ADDRESS_MAXIMUM_VALUE)
+            .contains(&address)
+        {
+            return Err(Error::InvalidParameter);
+        }
+        self.write_byte(Register::SlaveAddress, address).await
+    }
+
+    /// Apply a pending slave address change.
+    ///
+    /// Saves the settings, reboots the device and waits for it to come back
+    /// before switching this instance to the new address.
+    ///
+    /// # Arguments
+    /// * `pending`: value returned by [`TFLuna::set_slave_address()`].
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the device was rebooted and the instance now uses the new address.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error. The instance keeps the old address.
+
+    #[bisync]
+    pub async fn commit_address_change(
+        &mut self,
+        pending: AddressChangePending,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.save_settings().await?;
+        self.reboot().await?;
+        // Wait for the device to be ready again
+        self.delay.delay_ms(REBOOT_DELAY_MS).await;
+        self.address = pending.address;
+        self.device_seen = false;
+        Ok(())
+    }
+
+    /// Get the current power mode of the device.
+    ///
+    /// # Returns
+    /// * `Ok(PowerMode)`: current power mode.
+    /// * `Err(Error::InvalidState)`: if registers contain invalid values,
+    /// * `Err(Error::NotFound)`: if the device never answered and still doesn't after waking it up.
+    ///
+    /// # Notes
+    /// Reading registers will wake up the device from ultra-low power mode.
+    /// Avoid frequent calls when ultra-low power mode is expected.
+    ///
+    /// A device in ultra-low power mode doesn't acknowledge reads, but neither does a device
+    /// at a wrong address. If the device never acknowledged a transaction since the driver
+    /// was created, the signature is read after the wake-up delay to tell both cases apart.
+
+    #[bisync]
+    pub async fn get_power_mode(&mut self) -> Result<PowerMode, Error<I2C::Error>> {
+        let power_saving_mode_value = self
+            .read_enumerated_byte(Register::PowerSavingMode, 1, false)
+            .await;
+
+        let result = match power_saving_mode_value {
+            Ok(0x00) => Ok(PowerMode::Normal),
+            Ok(0x01) => Ok(PowerMode::PowerSaving),
+            Ok(_) | Err(Error::InvalidData(_)) => Err(Error::InvalidState),
             Err(e) => {
                 match e {
                     Error::<I2C::Error>::I2c(e) => {
                         // Check if the I2C error is a NoAcknowledge error
                         if let ErrorKind::NoAcknowledge(_) = e.kind() {
+                            self.confirm_asleep().await?;
                             Ok(PowerMode::UltraLow)
                         } else {
                             // Return the original I2C error for other error kinds
@@ -395,6 +1153,32 @@ where
                     _ => Err(e),
                 }
             }
+        };
+        self.power_mode = result.as_ref().ok().copied();
+        result
+    }
+
+    /// Check that a NACK came from a device in ultra-low power mode rather than from a missing device.
+    ///
+    /// The NACKed read already woke up a device in ultra-low power mode,
+    /// so it must answer the signature read after the wake-up delay.
+    #[bisync]
+    async fn confirm_asleep(&mut self) -> Result<(), Error<I2C::Error>> {
+        if self.device_seen {
+            return Ok(());
+        }
+        self.delay
+            .delay_ms(constants::ULTRA_LOW_POWER_WAKE_UP_DELAY_MS)
+            .await;
+        let mut signature = [0; 4];
+        match self
+            .read_once(Register::Signature as u8, &mut signature)
+            .await
+        {
+            Ok(()) if signature == SIGNATURE => Ok(()),
+            Ok(()) => Err(Error::NotFound),
+            Err(e) if Self::is_nack(&e) => Err(Error::NotFound),
+            Err(e) => Err(e),
         }
     }
 
@@ -406,23 +1190,80 @@ where
     /// # Notes
     /// * Power saving modes may reduce power consumption at the cost of performance.
     /// * Do not send setup commands while in ultra-low power mode.
+    /// * With the `log-transitions` feature, the transition is logged. The previous mode is
+    ///   the one last set or read through the driver, `None` if unknown.
+    /// * The default [`PowerModeTiming`] is used, see [`TFLuna::set_power_mode_with_timing()`].
 
     #[bisync]
     pub async fn set_power_mode(&mut self, mode: PowerMode) -> Result<(), Error<I2C::Error>> {
+        self.set_power_mode_with_timing(mode, PowerModeTiming::default())
+            .await
+    }
+
+    /// Set the power mode of the device, with custom delays.
+    ///
+    /// # Arguments
+    /// * `mode`: desired power mode.
+    /// * `timing`: delays to apply during the transition.
+    ///
+    /// # Notes
+    /// * Entering or leaving [`PowerMode::UltraLow`] writes the ultra-low power mode register,
+    ///   waits `timing.save_ms` after saving the settings, reboots the device and waits
+    ///   `timing.reboot_ms`. Switching between [`PowerMode::Normal`] and
+    ///   [`PowerMode::PowerSaving`] doesn't reboot the device, so these two delays don't apply.
+    /// * Every transition then waits `timing.settle_ms`.
+    /// * Leaving [`PowerMode::UltraLow`] additionally waits 12ms after waking up the device,
+    ///   as per the manual, see [`TFLuna::wake_from_ultra_low_power()`].
+
+    #[bisync]
+    pub async fn set_power_mode_with_timing(
+        &mut self,
+        mode: PowerMode,
+        timing: PowerModeTiming,
+    ) -> Result<(), Error<I2C::Error>> {
+        log_transition!("Power mode transition: {} -> {}", self.power_mode, mode);
+        let result = self.write_power_mode(mode, timing).await;
+        match &result {
+            Ok(()) => {
+                log_transition!("Power mode transition to {} done", mode);
+                self.power_mode = Some(mode);
+            }
+            Err(_e) => {
+                log_transition!(
+                    "Power mode transition to {} failed: {}",
+                    mode,
+                    defmt::Debug2Format(_e)
+                );
+                self.power_mode = None;
+            }
+        }
+        result
+    }
+
+    #[bisync]
+    async fn write_power_mode(
+        &mut self,
+        mode: PowerMode,
+        timing: PowerModeTiming,
+    ) -> Result<(), Error<I2C::Error>> {
         match mode {
             PowerMode::Normal => {
-                self.disable_ultra_low_power_mode().await?;
+                self.disable_ultra_low_power_mode(timing).await?;
                 self.set_normal_power_mode().await?;
             }
             PowerMode::PowerSaving => {
-                self.disable_ultra_low_power_mode().await?;
+                self.disable_ultra_low_power_mode(timing).await?;
                 self.set_power_saving_mode().await?;
             }
             PowerMode::UltraLow => {
-                self.enable_ultra_low_power_mode().await?;
+                self.enable_ultra_low_power_mode(timing).await?;
             }
         }
-        self.delay.delay_ms(100).await;
+        log_transition!(
+            "Waiting {}ms for the power mode to take effect",
+            timing.settle_ms
+        );
+        self.delay.delay_ms(timing.settle_ms).await;
         Ok(())
     }
 
@@ -447,45 +1288,97 @@ where
     // Set ultra-low power mode, save settings and reboot
 
     #[bisync]
-    async fn enable_ultra_low_power_mode(&mut self) -> Result<(), Error<I2C::Error>> {
-        self.write(&[
-            Register::UltraLowPowerMode as u8,
-            constants::ULTRA_LOWER_POWER_MODE_COMMAND_VALUE,
-            constants::SAVE_COMMAND_VALUE,
-            constants::REBOOT_COMMAND_VALUE,
-        ])
-        .await?;
-        // Wait for a second for the device to be ready again
-        self.delay.delay_ms(1000).await;
-        Ok(())
+    async fn enable_ultra_low_power_mode(
+        &mut self,
+        timing: PowerModeTiming,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.write_ultra_low_power_mode(constants::ULTRA_LOWER_POWER_MODE_COMMAND_VALUE, timing)
+            .await
     }
 
     #[bisync]
-    async fn disable_ultra_low_power_mode(&mut self) -> Result<(), Error<I2C::Error>> {
+    async fn disable_ultra_low_power_mode(
+        &mut self,
+        timing: PowerModeTiming,
+    ) -> Result<(), Error<I2C::Error>> {
         self.wake_from_ultra_low_power().await?;
-        self.write::<4>(&[
-            Register::UltraLowPowerMode as u8,
-            constants::NORMAL_POWER_MODE_COMMAND_VALUE,
-            constants::SAVE_COMMAND_VALUE,
-            constants::REBOOT_COMMAND_VALUE,
-        ])
-        .await?;
-        // Wait for a second for the device to be ready again
-        self.delay.delay_ms(1000).await;
+        self.write_ultra_low_power_mode(constants::NORMAL_POWER_MODE_COMMAND_VALUE, timing)
+            .await
+    }
+
+    /// Write the ultra-low power mode register, then save settings and reboot.
+    ///
+    /// # Notes
+    /// * The ultra-low power mode setting only takes effect after a save and a reboot.
+    /// * Each register is written in its own transaction. Even though the three registers
+    ///   are adjacent, relying on a single multi-register write to execute both
+    ///   the save and reboot commands is not documented in the manual.
+    #[bisync]
+    async fn write_ultra_low_power_mode(
+        &mut self,
+        value: u8,
+        timing: PowerModeTiming,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.write_byte(Register::UltraLowPowerMode, value).await?;
+        self.save_settings().await?;
+        // Give the device time to persist the settings before rebooting
+        log_transition!(
+            "Ultra-low power mode set to {}, waiting {}ms before rebooting",
+            value,
+            timing.save_ms
+        );
+        self.delay.delay_ms(timing.save_ms).await;
+        self.reboot().await?;
+        // Wait for the device to be ready again
+        log_transition!(
+            "Rebooted, waiting {}ms for the device to be ready",
+            timing.reboot_ms
+        );
+        self.delay.delay_ms(timing.reboot_ms).await;
         Ok(())
     }
 
+    /// Check whether ultra-low power mode can be relied upon with the device's firmware.
+    ///
+    /// # Returns
+    /// * `Ok(true)`: if the firmware is not known to mishandle ultra-low power mode.
+    /// * `Ok(false)`: if the firmware is known to mishandle ultra-low power mode.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * This only reads the firmware version, the power mode is left untouched.
+    /// * Firmware 3.5.1 is known to be problematic: the device frequently fails to wake up
+    ///   or to return measurements after entering ultra-low power mode.
+
+    #[bisync]
+    pub async fn ultra_low_power_reliable(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let firmware_version = self.get_firmware_version().await?;
+        Ok(!constants::ULTRA_LOW_POWER_UNRELIABLE_FIRMWARE_VERSIONS.contains(&firmware_version))
+    }
+
     /// Wakes up device from ultra-low power mode.
     ///
     /// # Notes
     /// * This is only useful in [`PowerMode::UltraLow`] power mode.
     /// * If that is the case, the method waits for 12ms before returning.
-    /// * In other power modes, there is no delay.
+    /// * In other power modes, there is no delay. If the driver knows that the device is in
+    ///   [`PowerMode::Normal`] or [`PowerMode::PowerSaving`], i.e. the mode was last set or read
+    ///   through the driver, nothing is sent to the device either.
+    /// * Once ultra-low power mode was set or read through the driver, writing settings returns
+    ///   [`Error::UltraLowPowerActive`] until this method is called, see [`TFLuna::assume_awake()`].
+    ///   The power mode is then no longer considered known, as the device falls asleep again
+    ///   on its own.
 
     #[bisync]
     pub async fn wake_from_ultra_low_power(&mut self) -> Result<(), Error<I2C::Error>> {
+        if matches!(
+            self.power_mode,
+            Some(PowerMode::Normal | PowerMode::PowerSaving)
+        ) {
+            return Ok(());
+        }
         // Wake up by reading any register
-        match self.read_byte(Register::Distance).await {
+        let result = match self.read_once(Register::Distance as u8, &mut [0; 1]).await {
             Ok(_) => Ok(()),
             Err(e) => {
                 match e {
@@ -493,7 +1386,13 @@ where
                         // Check if the I2C error is a NoAcknowledge error
                         if let ErrorKind::NoAcknowledge(_) = e.kind() {
                             // Wait at least 12ms after awakening as per manual
-                            self.delay.delay_ms(12).await;
+                            log_transition!(
+                                "Woke up from ultra-low power mode, waiting {}ms",
+                                constants::ULTRA_LOW_POWER_WAKE_UP_DELAY_MS
+                            );
+                            self.delay
+                                .delay_ms(constants::ULTRA_LOW_POWER_WAKE_UP_DELAY_MS)
+                                .await;
                             Ok(())
                         } else {
                             // Return the original I2C error for other error kinds
@@ -503,7 +1402,82 @@ where
                     _ => Err(Error::Other),
                 }
             }
+        };
+        if result.is_ok() {
+            self.assume_awake();
+        }
+        result
+    }
+
+    /// Allow writing settings again after entering ultra-low power mode, without waking up the device.
+    ///
+    /// # Notes
+    /// * Only use this if the device was woken up by other means, e.g. by another controller
+    ///   instance. Writes to a sleeping device are not acknowledged.
+    pub fn assume_awake(&mut self) {
+        if self.power_mode == Some(PowerMode::UltraLow) {
+            self.power_mode = None;
+        }
+    }
+
+    /// Take a single measurement from a device in ultra-low power mode.
+    ///
+    /// Wakes the device up, triggers a measurement, reads it and lets the device go back to sleep,
+    /// which is the building block of battery-powered duty-cycled applications.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: measurement taken after waking up the device.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The device must have been put in ultra-low power mode beforehand with
+    ///   [`TFLuna::set_power_mode()`]. Check [`TFLuna::ultra_low_power_reliable()`] first,
+    ///   some firmware versions often fail to wake up or to return measurements in that mode.
+    /// * The timing is: 12ms wake-up delay as per the manual if the device was asleep, trigger,
+    ///   one frame period at the default 100Hz framerate (10ms) and read, i.e. about 22ms awake
+    ///   plus the I2C transfers. The framerate is not read to keep the device awake as little
+    ///   as possible.
+    /// * If the ranging mode is not known to be [`RangingMode::Trigger`], it is read once and
+    ///   the device is switched to trigger mode if needed, waiting 100ms for the switch to take
+    ///   effect. Save the settings in trigger mode before entering ultra-low power mode to skip this.
+    /// * The device goes back to sleep on its own once the I2C communication stops,
+    ///   no command is sent for that. If ultra-low power mode was entered through the driver,
+    ///   writing settings returns [`Error::UltraLowPowerActive`] again afterwards, as it does
+    ///   before the first call.
+
+    #[bisync]
+    pub async fn duty_cycle_read(&mut self) -> Result<SensorReading, Error<I2C::Error>> {
+        let asleep = self.power_mode == Some(PowerMode::UltraLow);
+        self.wake_from_ultra_low_power().await?;
+        let result = self.trigger_and_read_awake().await;
+        // The device drops back to sleep once the I2C communication stops, even after an error
+        if asleep {
+            self.power_mode = Some(PowerMode::UltraLow);
+        }
+        result
+    }
+
+    /// Make sure the device is in trigger mode, trigger a measurement and read it,
+    /// for [`TFLuna::duty_cycle_read()`] once the device is awake.
+    #[bisync]
+    async fn trigger_and_read_awake(&mut self) -> Result<SensorReading, Error<I2C::Error>> {
+        if self.ranging_mode != Some(RangingMode::Trigger)
+            && self.get_ranging_mode().await? == RangingMode::Continuous
+        {
+            self.set_ranging_mode(RangingMode::Trigger).await?;
+            log_transition!(
+                "Waiting {}ms for the ranging mode to take effect",
+                constants::RANGING_MODE_SWITCH_DELAY_MS
+            );
+            self.delay
+                .delay_ms(constants::RANGING_MODE_SWITCH_DELAY_MS)
+                .await;
         }
+        self.trigger_measurement().await?;
+        self.delay
+            .delay_ms(1000u32.div_ceil(constants::DEFAULT_FRAMERATE as u32))
+            .await;
+        self.read_triggered().await
     }
 
     /// Get the current ranging mode of the device.
@@ -515,12 +1489,16 @@ where
 
     #[bisync]
     pub async fn get_ranging_mode(&mut self) -> Result<RangingMode, Error<I2C::Error>> {
-        let mode = self.read_byte(Register::RangingMode).await?;
-        match mode {
-            val if val == RangingMode::Continuous as u8 => Ok(RangingMode::Continuous),
-            val if val == RangingMode::Trigger as u8 => Ok(RangingMode::Trigger),
-            _ => Err(Error::<I2C::Error>::InvalidData(mode)),
-        }
+        let mode = self
+            .read_enumerated_byte(Register::RangingMode, 1, true)
+            .await?;
+        let mode = match mode {
+            val if val == RangingMode::Continuous as u8 => RangingMode::Continuous,
+            val if val == RangingMode::Trigger as u8 => RangingMode::Trigger,
+            _ => return Err(Error::<I2C::Error>::InvalidData(mode)),
+        };
+        self.ranging_mode = Some(mode);
+        Ok(mode)
     }
 
     /// Set the ranging mode of the device.
@@ -533,11 +1511,74 @@ where
     /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
     ///
     /// # Notes
-    /// In [`RangingMode::Trigger`] mode, use [`TFLuna::trigger_measurement()`] to initiate measurements.
+    /// * In [`RangingMode::Trigger`] mode, use [`TFLuna::trigger_measurement()`] to initiate measurements.
+    /// * With the `log-transitions` feature, the transition is logged. The previous mode is
+    ///   the one last set or read through the driver, `None` if it is unknown.
 
     #[bisync]
     pub async fn set_ranging_mode(&mut self, mode: RangingMode) -> Result<(), Error<I2C::Error>> {
-        self.write_byte(Register::RangingMode, mode as u8).await
+        log_transition!("Ranging mode transition: {} -> {}", self.ranging_mode, mode);
+        let result = self.write_byte(Register::RangingMode, mode as u8).await;
+        if let Err(_e) = &result {
+            log_transition!(
+                "Ranging mode transition to {} failed: {}",
+                mode,
+                defmt::Debug2Format(_e)
+            );
+        }
+        self.ranging_mode = result.is_ok().then_some(mode);
+        result
+    }
+
+    /// Stop ranging without disabling the device.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the device stopped ranging.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The TF-Luna has no dedicated idle mode. This switches to [`RangingMode::Trigger`],
+    ///   in which the device stops measuring on its own but stays enabled and responsive,
+    ///   so no re-enabling is needed to get measurements again.
+    /// * Use [`TFLuna::resume()`] to go back to continuous ranging, or
+    ///   [`TFLuna::trigger_measurement()`] to take single measurements while idle.
+    /// * In contrast, [`TFLuna::disable()`] stops measurements entirely and they only
+    ///   resume after [`TFLuna::enable()`].
+    /// * Idling is temporary, so it isn't reported by [`TFLuna::has_unsaved_changes()`].
+    ///   Saving the settings while idle still saves the trigger ranging mode.
+
+    #[bisync]
+    pub async fn set_idle(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.set_ranging_mode_untracked(RangingMode::Trigger).await
+    }
+
+    /// Resume continuous ranging after [`TFLuna::set_idle()`].
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the device resumed ranging.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The first new measurement is available after one frame period,
+    ///   i.e. `1000 / framerate` milliseconds.
+    /// * Like [`TFLuna::set_idle()`], this isn't reported by [`TFLuna::has_unsaved_changes()`].
+
+    #[bisync]
+    pub async fn resume(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.set_ranging_mode_untracked(RangingMode::Continuous)
+            .await
+    }
+
+    /// Set the ranging mode without recording it as an unsaved change.
+    #[bisync]
+    async fn set_ranging_mode_untracked(
+        &mut self,
+        mode: RangingMode,
+    ) -> Result<(), Error<I2C::Error>> {
+        let unsaved_changes = self.unsaved_changes;
+        let result = self.set_ranging_mode(mode).await;
+        self.unsaved_changes = unsaved_changes;
+        result
     }
 
     /// Get the current measurement framerate in Hz.
@@ -551,6 +1592,79 @@ where
         self.read_word(Register::Framerate).await
     }
 
+    /// Get the framerate in Hz the device is actually measuring at, given its power mode.
+    ///
+    /// # Returns
+    /// * `Ok(u16)`: effective framerate in Hz.
+    /// * `Err(Error::InvalidState)`: if the power mode register contains an invalid value.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * In [`PowerMode::Normal`], the effective framerate is the configured framerate.
+    /// * In [`PowerMode::PowerSaving`], the framerate is capped at 10Hz, even if
+    ///   a higher framerate is configured.
+    /// * In [`PowerMode::UltraLow`], `0` is returned since the device sleeps and only measures
+    ///   when woken up. The framerate register is not read in that case to avoid waking it up.
+
+    #[bisync]
+    pub async fn get_effective_framerate(&mut self) -> Result<u16, Error<I2C::Error>> {
+        match self.get_power_mode().await? {
+            PowerMode::Normal => self.get_framerate().await,
+            PowerMode::PowerSaving => {
+                let framerate = self.get_framerate().await?;
+                Ok(framerate.min(Self::max_framerate_in(PowerMode::PowerSaving)))
+            }
+            PowerMode::UltraLow => Ok(0),
+        }
+    }
+
+    /// Get the highest framerate in Hz reachable in the current power mode.
+    ///
+    /// # Returns
+    /// * `Ok(u16)`: highest reachable framerate in Hz.
+    /// * `Err(Error::InvalidState)`: if the power mode register contains an invalid value.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * In [`PowerMode::Normal`], this is the highest framerate accepted by
+    ///   [`TFLuna::set_framerate()`], i.e. 250Hz.
+    /// * In [`PowerMode::PowerSaving`], this is 10Hz.
+    /// * In [`PowerMode::UltraLow`], this is `0` since the device only measures when woken up.
+
+    #[bisync]
+    pub async fn max_framerate(&mut self) -> Result<u16, Error<I2C::Error>> {
+        let mode = self.get_power_mode().await?;
+        Ok(Self::max_framerate_in(mode))
+    }
+
+    /// Highest framerate in Hz reachable in the given power mode.
+    fn max_framerate_in(mode: PowerMode) -> u16 {
+        match mode {
+            PowerMode::Normal => FRAMERATE_OPTIONS[FRAMERATE_OPTIONS.len() - 1],
+            PowerMode::PowerSaving => constants::POWER_SAVING_MAXIMUM_FRAMERATE,
+            PowerMode::UltraLow => 0,
+        }
+    }
+
+    /// Check whether the output measurements are averaged by the device.
+    ///
+    /// # Returns
+    /// * `Ok(true)`: if each output measurement is the average of several internal measurements.
+    /// * `Ok(false)`: if each output measurement is a single internal measurement.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The device measures at 500Hz internally. When the framerate is lower,
+    ///   the internal measurements taken during one frame period are averaged,
+    ///   e.g. 5 measurements per output at 100Hz. This reduces the noise of the output.
+    /// * A framerate of 0 (no continuous output) is reported as not averaged.
+
+    #[bisync]
+    pub async fn is_averaged(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let framerate = self.get_framerate().await?;
+        Ok(framerate != 0 && framerate < constants::INTERNAL_MEASUREMENT_RATE)
+    }
+
     /// Set the measurement framerate in Hz.
     ///
     /// # Arguments
@@ -562,18 +1676,53 @@ where
     /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
     ///
     /// # Notes
-    /// * Only factors of 500Hz / n, where n in [2, 3, ...], are allowed.
+    /// * Only the framerates listed in the manual are allowed, from 1Hz to 250Hz.
+    ///   See [`TFLuna::framerate_options()`] for the full list.
+    /// * A framerate of 0 is also accepted. It stops the continuous output, so the device only
+    ///   produces a new measurement when triggered with [`TFLuna::trigger_measurement()`],
+    ///   like in [`RangingMode::Trigger`]. Prefer [`TFLuna::set_ranging_mode()`] to switch
+    ///   to trigger mode, since it keeps the framerate used when going back to continuous
+    ///   ranging, which a framerate of 0 does not.
 
     #[bisync]
     pub async fn set_framerate(&mut self, value: u16) -> Result<(), Error<I2C::Error>> {
         match value {
-            x if x == 0 || (x < 500 && (500 % x) == 0) => {
+            x if x == 0 || FRAMERATE_OPTIONS.contains(&x) => {
                 self.write_word(Register::Framerate, value).await
             }
             _ => Err(Error::<I2C::Error>::InvalidParameter),
         }
     }
 
+    /// Get the framerates, in Hz, that can be passed to [`TFLuna::set_framerate()`].
+    ///
+    /// # Returns
+    /// * `&'static [u16]`: valid framerates in ascending order.
+    pub fn framerate_options() -> &'static [u16] {
+        &FRAMERATE_OPTIONS
+    }
+
+    /// Get the expected resolution, in centimeters, of a measurement at the given distance.
+    ///
+    /// # Arguments
+    /// * `distance`: measured distance in centimeters.
+    ///
+    /// # Returns
+    /// * `u16`: smallest change in distance that can be told apart from noise at `distance`.
+    ///
+    /// # Notes
+    /// * Distances are reported in steps of 1cm, but the actual resolution is limited by
+    ///   the accuracy given in the manual: ±6cm between 0.2m and 3m, and ±2% between 3m and 8m.
+    ///   This function implements that model, rounding the relative accuracy up.
+    /// * Outside of the operating range the closest band of the model is used.
+    pub fn distance_resolution_cm(&self, distance: u16) -> u16 {
+        if distance <= constants::FIXED_ACCURACY_MAXIMUM_DISTANCE {
+            constants::FIXED_ACCURACY
+        } else {
+            (distance as u32 * constants::RELATIVE_ACCURACY_PERCENT as u32).div_ceil(100) as u16
+        }
+    }
+
     /// Get the current signal strength threshold.
     ///
     /// # Returns
@@ -636,6 +1785,23 @@ where
         self.write_word(Register::DummyDistance, value).await
     }
 
+    /// Get the signal strength threshold and the dummy distance together.
+    ///
+    /// # Returns
+    /// * `Ok((u16, u16))`: current `(threshold, dummy_distance)` pair.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// When Signal Strength < Signal Strength Threshold * 10,
+    /// then the returned distance is the dummy distance instead of the actual distance
+
+    #[bisync]
+    pub async fn get_dummy_behavior(&mut self) -> Result<(u16, u16), Error<I2C::Error>> {
+        let threshold = self.get_signal_strength_threshold().await?;
+        let dummy_distance = self.get_dummy_distance().await?;
+        Ok((threshold, dummy_distance))
+    }
+
     /// Get the current maximum distance setting.
     ///
     /// # Returns
@@ -692,17 +1858,440 @@ where
         self.write_word(Register::MaximumDistance, value).await
     }
 
-    /// Get the error code from the device.
+    /// Get a word-sized configuration value.
+    ///
+    /// # Arguments
+    /// * `field`: configuration value to read.
     ///
     /// # Returns
-    /// * `Ok(u16)`: Error code value.
+    /// * `Ok(u16)`: current value.
     /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
-
+    ///
+    /// # Notes
+    /// * This is equivalent to calling the dedicated getter, e.g. [`TFLuna::get_framerate()`]
+    ///   for [`ConfigWord::Framerate`].
+
+    #[bisync]
+    pub async fn get_config_word(&mut self, field: ConfigWord) -> Result<u16, Error<I2C::Error>> {
+        match field {
+            ConfigWord::Framerate => self.get_framerate().await,
+            ConfigWord::SignalStrengthThreshold => self.get_signal_strength_threshold().await,
+            ConfigWord::DummyDistance => self.get_dummy_distance().await,
+            ConfigWord::MinimumDistance => self.get_minimum_distance().await,
+            ConfigWord::MaximumDistance => self.get_maximum_distance().await,
+        }
+    }
+
+    /// Set a word-sized configuration value.
+    ///
+    /// # Arguments
+    /// * `field`: configuration value to write.
+    /// * `value`: new value.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the value was set successfully.
+    /// * `Err(Error::InvalidParameter)`: if the value is not valid for this field.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * This is equivalent to calling the dedicated setter, e.g. [`TFLuna::set_framerate()`]
+    ///   for [`ConfigWord::Framerate`], and performs the same validation.
+
+    #[bisync]
+    pub async fn set_config_word(
+        &mut self,
+        field: ConfigWord,
+        value: u16,
+    ) -> Result<(), Error<I2C::Error>> {
+        match field {
+            ConfigWord::Framerate => self.set_framerate(value).await,
+            ConfigWord::SignalStrengthThreshold => self.set_signal_strength_threshold(value).await,
+            ConfigWord::DummyDistance => self.set_dummy_distance(value).await,
+            ConfigWord::MinimumDistance => self.set_minimum_distance(value).await,
+            ConfigWord::MaximumDistance => self.set_maximum_distance(value).await,
+        }
+    }
+
+    /// Read a single register by address.
+    ///
+    /// # Arguments
+    /// * `register`: register address.
+    ///
+    /// # Returns
+    /// * `Ok(u8)`: register content.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Meant for registers that have no dedicated method, e.g. firmware-specific ones.
+    ///   Prefer the dedicated methods otherwise.
+
+    #[bisync]
+    pub async fn read_register(&mut self, register: u8) -> Result<u8, Error<I2C::Error>> {
+        let mut buffer = [0; 1];
+        self.read(register, &mut buffer).await?;
+        Ok(buffer[0])
+    }
+
+    /// Write a single register by address.
+    ///
+    /// # Arguments
+    /// * `register`: register address.
+    /// * `value`: value to write.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the write was successful.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Meant for registers that have no dedicated method, e.g. firmware-specific ones.
+    ///   Prefer the dedicated methods otherwise.
+    /// * The write bypasses the state kept by the driver, e.g. it is not reported
+    ///   by [`TFLuna::has_unsaved_changes()`] and is not refused in ultra-low power mode.
+
+    #[bisync]
+    pub async fn write_register(
+        &mut self,
+        register: u8,
+        value: u8,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.write(&[register, value]).await
+    }
+
+    /// Read the whole device configuration.
+    ///
+    /// # Returns
+    /// * `Ok(Config)`: current configuration.
+    /// * `Err(Error::InvalidData)`: if a register contains an invalid value.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * A device in ultra-low power mode doesn't answer, wake it up first with
+    ///   [`TFLuna::wake_from_ultra_low_power()`].
+
+    #[bisync]
+    pub async fn get_config(&mut self) -> Result<Config, Error<I2C::Error>> {
+        let framerate = self.get_framerate().await?;
+        let ranging_mode = self.get_ranging_mode().await?;
+        let signal_strength_threshold = self.get_signal_strength_threshold().await?;
+        let dummy_distance = self.get_dummy_distance().await?;
+        let minimum_distance = self.get_minimum_distance().await?;
+        let maximum_distance = self.get_maximum_distance().await?;
+        let power_mode = self.get_power_mode().await?;
+        Ok(Config {
+            framerate,
+            ranging_mode,
+            power_mode,
+            signal_strength_threshold,
+            dummy_distance,
+            minimum_distance,
+            maximum_distance,
+        })
+    }
+
+    /// Write the whole device configuration.
+    ///
+    /// # Arguments
+    /// * `config`: configuration to write, e.g. as read by [`TFLuna::get_config()`].
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the configuration was applied successfully.
+    /// * `Err(Error::InvalidParameter)`: if the framerate is invalid.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The power mode is set first because changing it saves the settings and reboots the device.
+    ///   The ranging mode, framerate, signal strength threshold, dummy distance, minimum distance
+    ///   and maximum distance are then written in that order.
+    /// * With [`PowerMode::UltraLow`], the power mode is set last instead, since settings
+    ///   can't be written once the device is in ultra-low power mode. The other values are
+    ///   then saved along with the power mode.
+    /// * The first failed write aborts the call, the values written before it are kept.
+    /// * The values written after the power mode are not saved. Call [`TFLuna::save_settings()`]
+    ///   to keep them across reboots.
+
+    #[bisync]
+    pub async fn apply_config(&mut self, config: &Config) -> Result<(), Error<I2C::Error>> {
+        if config.power_mode == PowerMode::UltraLow {
+            self.write_config_settings(config).await?;
+            return self.set_power_mode(config.power_mode).await;
+        }
+        self.set_power_mode(config.power_mode).await?;
+        self.write_config_settings(config).await
+    }
+
+    /// Write all the settings of a configuration but the power mode.
+    #[bisync]
+    async fn write_config_settings(&mut self, config: &Config) -> Result<(), Error<I2C::Error>> {
+        self.set_ranging_mode(config.ranging_mode).await?;
+        self.set_framerate(config.framerate).await?;
+        self.set_signal_strength_threshold(config.signal_strength_threshold)
+            .await?;
+        self.set_dummy_distance(config.dummy_distance).await?;
+        self.set_minimum_distance(config.minimum_distance).await?;
+        self.set_maximum_distance(config.maximum_distance).await
+    }
+
+    /// Apply a named configuration.
+    ///
+    /// This is equivalent to [`TFLuna::apply_config()`] with [`Preset::config()`].
+    ///
+    /// # Arguments
+    /// * `preset`: configuration to apply, see [`Preset`] for the values of each preset.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the configuration was applied successfully.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Setting the power mode saves the settings and reboots the device, the other values
+    ///   are written afterwards and not saved. Call [`TFLuna::save_settings()`] to keep them
+    ///   across reboots. Presets in [`PowerMode::UltraLow`] are the exception, see
+    ///   [`TFLuna::apply_config()`].
+
+    #[bisync]
+    pub async fn apply_preset(&mut self, preset: Preset) -> Result<(), Error<I2C::Error>> {
+        self.apply_config(&preset.config()).await
+    }
+
+    /// Verify that the device is provisioned as expected.
+    ///
+    /// Checks, in order, that:
+    /// 1. The driver and the device's slave address register both use `expected_address`.
+    /// 2. The device signature is 'L' 'U' 'N' 'A'.
+    /// 3. The firmware version is sane, i.e. neither all zeros nor all ones.
+    /// 4. Each configuration value matches `expected`.
+    ///
+    /// # Arguments
+    /// * `expected`: expected configuration.
+    /// * `expected_address`: expected I2C slave address.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if all checks passed.
+    /// * `Err(Error::ProvisioningMismatch(ProvisioningCheck))`: on the first check that failed.
+    /// * `Err(Error::InvalidData)`: if a register contains an invalid value.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+
+    #[bisync]
+    pub async fn verify_provisioning(
+        &mut self,
+        expected: &Config,
+        expected_address: u8,
+    ) -> Result<(), Error<I2C::Error>> {
+        let mismatch = |check| Err(Error::ProvisioningMismatch(check));
+        if u8::from(self.address) != expected_address
+            || self.get_slave_address().await? != expected_address
+        {
+            return mismatch(ProvisioningCheck::Address);
+        }
+        if self.get_signature().await?.0 != SIGNATURE {
+            return mismatch(ProvisioningCheck::Signature);
+        }
+        let version = self.get_firmware_version().await?;
+        let version = [version.major, version.minor, version.revision];
+        if version == [0; 3] || version == [u8::MAX; 3] {
+            return mismatch(ProvisioningCheck::FirmwareVersion);
+        }
+        let config = self.get_config().await?;
+        let checks = [
+            (
+                config.framerate == expected.framerate,
+                ProvisioningCheck::Framerate,
+            ),
+            (
+                config.ranging_mode == expected.ranging_mode,
+                ProvisioningCheck::RangingMode,
+            ),
+            (
+                config.signal_strength_threshold == expected.signal_strength_threshold,
+                ProvisioningCheck::SignalStrengthThreshold,
+            ),
+            (
+                config.dummy_distance == expected.dummy_distance,
+                ProvisioningCheck::DummyDistance,
+            ),
+            (
+                config.minimum_distance == expected.minimum_distance,
+                ProvisioningCheck::MinimumDistance,
+            ),
+            (
+                config.maximum_distance == expected.maximum_distance,
+                ProvisioningCheck::MaximumDistance,
+            ),
+            (
+                config.power_mode == expected.power_mode,
+                ProvisioningCheck::PowerMode,
+            ),
+        ];
+        match checks.into_iter().find(|(matches, _)| !matches) {
+            Some((_, check)) => mismatch(check),
+            None => Ok(()),
+        }
+    }
+
+    /// Get the error code from the device.
+    ///
+    /// # Returns
+    /// * `Ok(u16)`: Error code value.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+
     #[bisync]
     pub async fn get_error(&mut self) -> Result<u16, Error<I2C::Error>> {
         self.read_word(Register::Error).await
     }
 
+    /// Get the number of device ticks elapsed since the previous call to this method.
+    ///
+    /// # Returns
+    /// * `Ok(u16)`: elapsed ticks, `0` on the first call.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The delta is computed with wrapping arithmetic, so it stays correct when
+    ///   the 16-bit timestamp register overflows between two calls, as long as
+    ///   less than 65536 ticks elapse between them.
+    /// * Comparing the delta with the expected frame period allows detecting missed frames.
+    /// * Ticks are converted to seconds by dividing them by [`DEVICE_TICK_HZ`](crate::i2c::DEVICE_TICK_HZ).
+
+    #[bisync]
+    pub async fn ticks_since_last_read(&mut self) -> Result<u16, Error<I2C::Error>> {
+        let timestamp = self.read_word(Register::Timestamp).await?;
+        let elapsed = match self.timestamps.last_read {
+            Some(last_timestamp) => timestamp.wrapping_sub(last_timestamp),
+            None => 0,
+        };
+        self.timestamps.last_read = Some(timestamp);
+        Ok(elapsed)
+    }
+
+    /// Check whether a new measurement is available since the previous call and clear the flag.
+    ///
+    /// # Returns
+    /// * `Ok(true)`: if the device produced a new measurement since the previous call,
+    ///   always the case on the first call.
+    /// * `Ok(false)`: if no new measurement was produced since the previous call.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The device has no sticky data-ready register. The flag is implemented in software
+    ///   by comparing the timestamp of the latest measurement with the one seen on the previous
+    ///   call, so it only tells whether at least one new measurement was produced,
+    ///   not how many.
+    /// * Unlike the data-ready pin, the flag is only cleared by this method, so it cannot
+    ///   miss a measurement that was produced between two calls.
+
+    #[bisync]
+    pub async fn take_new_data_flag(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let timestamp = self.read_word(Register::Timestamp).await?;
+        let new_data = self.timestamps.new_data != Some(timestamp);
+        self.timestamps.new_data = Some(timestamp);
+        Ok(new_data)
+    }
+
+    /// Record the current device timestamp as a common sync point.
+    ///
+    /// # Returns
+    /// * `Ok(TimestampBase)`: time base relative to the current timestamp.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The device timestamp is the uptime in ticks and cannot be reset. To align several
+    ///   devices, call this method on each of them at the same time (e.g. back-to-back)
+    ///   and use [`TimestampBase::align()`] on their measurements.
+
+    #[bisync]
+    pub async fn sync_timestamp_base(&mut self) -> Result<TimestampBase, Error<I2C::Error>> {
+        let timestamp = self.read_word(Register::Timestamp).await?;
+        Ok(TimestampBase::new(timestamp))
+    }
+
+    /// Get the internal device temperature in °C, without reading the rest of the measurement.
+    ///
+    /// # Returns
+    /// * `Ok(f32)`: temperature in °C, the same value as [`SensorReading::temperature`].
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * This is the internal chip temperature, usually a few degrees above the ambient temperature.
+
+    #[bisync]
+    pub async fn get_temperature(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let (_, celsius) = self.get_temperature_detailed().await?;
+        Ok(celsius)
+    }
+
+    /// Get the internal device temperature both as raw register value and in °C.
+    ///
+    /// # Returns
+    /// * `Ok((u16, f32))`: `(raw_centidegrees, celsius)` pair, where `raw_centidegrees` is the
+    ///   unmodified content of registers 0x04 and 0x05.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Meant for diagnostics, the raw value is signed (two's complement) in 0.01°C units
+    ///   and `celsius` is the same value as [`SensorReading::temperature`].
+
+    #[bisync]
+    pub async fn get_temperature_detailed(&mut self) -> Result<(u16, f32), Error<I2C::Error>> {
+        let raw = self.read_word(Register::Temperature).await?;
+        Ok((raw, raw as i16 as f32 / 100.0))
+    }
+
+    /// Get the distance in centimeters, with the minimal 2-byte read.
+    ///
+    /// # Returns
+    /// * `Ok(u16)`: distance in centimeters.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * This is the same read as [`TFLuna::get_filtered_distance()`], see its notes on
+    ///   the processing applied by the device.
+
+    #[bisync]
+    pub async fn get_distance(&mut self) -> Result<u16, Error<I2C::Error>> {
+        self.get_filtered_distance().await
+    }
+
+    /// Get the distance output by the device, without reading the rest of the measurement.
+    ///
+    /// # Returns
+    /// * `Ok(u16)`: distance in centimeters.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The distance registers hold the output of the device, after its own processing.
+    ///   The limits set with [`TFLuna::set_minimum_distance()`], [`TFLuna::set_maximum_distance()`]
+    ///   and the dummy distance reported below [`TFLuna::set_signal_strength_threshold()`]
+    ///   are applied by the device to this output, the same one that is sent over UART.
+    /// * The driver doesn't filter the distance itself, the value is returned as read.
+    ///   Use [`TFLuna::get_measurement()`] to also get the signal strength and error code
+    ///   needed to tell a real distance apart from the dummy distance.
+
+    #[bisync]
+    pub async fn get_filtered_distance(&mut self) -> Result<u16, Error<I2C::Error>> {
+        self.read_word(Register::Distance).await
+    }
+
+    /// Get the distance and the signal strength, without reading the rest of the measurement.
+    ///
+    /// # Returns
+    /// * `Ok((u16, u16))`: `(distance, signal_strength)` pair, distance in centimeters.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Reads registers 0x00 to 0x03 in a single 4-byte read, instead of the 10 bytes read
+    ///   by [`TFLuna::get_measurement()`], which matters at high framerates on a 100kHz bus.
+
+    #[bisync]
+    pub async fn get_distance_and_strength(&mut self) -> Result<(u16, u16), Error<I2C::Error>> {
+        let mut buffer = [0; 4];
+        self.read::<4>(Register::Distance as u8, &mut buffer)
+            .await?;
+        Ok((
+            self.combine_buffer_into_word(&[buffer[0], buffer[1]]),
+            self.combine_buffer_into_word(&[buffer[2], buffer[3]]),
+        ))
+    }
+
     /// Perform a complete measurement reading from the sensor.
     ///
     /// # Returns
@@ -718,40 +2307,1021 @@ where
     ///   - Error: Registers 0x08 (low byte) and 0x09 (high byte) error code
     ///
     /// * Temperature is automatically converted from hundredths of degrees Celsius to degrees Celsius.
+    ///   The raw value is signed (two's complement) to support temperatures below 0°C.
 
     #[bisync]
     pub async fn get_measurement(&mut self) -> Result<SensorReading, Error<I2C::Error>> {
         let mut buffer = [0; 10];
-        self.read::<10>(Register::Distance, &mut buffer).await?;
-        let distance = self.combine_buffer_into_word(&[buffer[0], buffer[1]]);
-        let signal_strength = self.combine_buffer_into_word(&[buffer[2], buffer[3]]);
-        let temperature = self.combine_buffer_into_word(&[buffer[4], buffer[5]]);
-        let temperature = temperature as f32 / 100.0;
-        let timestamp = self.combine_buffer_into_word(&[buffer[6], buffer[7]]);
-        let error = self.combine_buffer_into_word(&[buffer[8], buffer[9]]);
-        Ok(SensorReading {
-            distance,
-            signal_strength,
-            temperature,
-            timestamp,
-            error,
-        })
+        self.read::<10>(Register::Distance as u8, &mut buffer)
+            .await?;
+        Ok(MeasurementView::new(&buffer).to_reading())
+    }
+
+    /// Perform a complete measurement reading into `buffer` and return a view over it.
+    ///
+    /// # Arguments
+    /// * `buffer`: buffer the 10 measurement bytes are read into.
+    ///
+    /// # Returns
+    /// * `Ok(MeasurementView)`: view decoding the fields from `buffer` on demand.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Like [`TFLuna::get_measurement()`], this is a single 10-byte read, but fields are
+    ///   only decoded when accessed. The view borrows `buffer`, so it is tied to this read
+    ///   and the buffer can only be reused once the view is dropped.
+
+    #[bisync]
+    pub async fn read_measurement_view<'a>(
+        &mut self,
+        buffer: &'a mut [u8; 10],
+    ) -> Result<MeasurementView<'a>, Error<I2C::Error>> {
+        self.read::<10>(Register::Distance as u8, buffer).await?;
+        Ok(MeasurementView::new(buffer))
+    }
+
+    /// Perform a measurement and get its distance as a fraction of the configured maximum distance.
+    ///
+    /// # Returns
+    /// * `Ok(f32)`: distance divided by the maximum distance, clamped to `0.0..=1.0`.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The maximum distance is read from the device on every call,
+    ///   see [`TFLuna::get_maximum_distance()`] and [`SensorReading::distance_fraction()`].
+
+    #[bisync]
+    pub async fn distance_fraction_auto(&mut self) -> Result<f32, Error<I2C::Error>> {
+        let max = self.get_maximum_distance().await?;
+        let measurement = self.get_measurement().await?;
+        Ok(measurement.distance_fraction(max))
+    }
+
+    /// Perform a complete measurement reading and apply a policy to the distance when the signal is lost.
+    ///
+    /// # Arguments
+    /// * `policy`: how the distance is reported when the signal is lost.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: measurement, with the distance replaced according to `policy`
+    ///   if the signal was lost.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The signal is considered lost when the device reports an error or the signal strength
+    ///   is below 100 or saturated (65535). Only the distance is replaced, the other fields
+    ///   are those of the current measurement.
+    /// * The driver retains the last reliable distance read by this method for [`LossPolicy::HoldLast`].
+    ///   Measurements read with other methods are not taken into account.
+
+    #[bisync]
+    pub async fn get_measurement_with_policy(
+        &mut self,
+        policy: LossPolicy,
+    ) -> Result<SensorReading, Error<I2C::Error>> {
+        let mut measurement = self.get_measurement().await?;
+        if measurement.is_valid() {
+            self.last_valid_distance = Some(measurement.distance);
+        } else {
+            measurement.distance = match policy {
+                LossPolicy::HoldLast => self.last_valid_distance.unwrap_or(0),
+                LossPolicy::Dummy(distance) => distance,
+                LossPolicy::Zero => 0,
+            };
+        }
+        Ok(measurement)
+    }
+
+    /// Get a measurement along with a 64-bit timestamp that doesn't wrap around.
+    ///
+    /// # Returns
+    /// * `Ok((u64, SensorReading))`: device ticks since the device was powered on, see
+    ///   [`crate::i2c::DEVICE_TICK_HZ`], and the measurement.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * [`SensorReading::timestamp`] wraps around every 65536 ticks, i.e. about 65s.
+    ///   The wraparounds are counted between calls, which assumes this method is called
+    ///   at least once per wraparound period. A longer gap silently loses whole periods.
+    /// * The first call starts from the raw timestamp, later calls add the ticks elapsed
+    ///   since the previous call.
+    /// * The device restarts counting from 0 after a reboot, which can't be told apart
+    ///   from a wraparound.
+
+    #[bisync]
+    pub async fn get_measurement_monotonic(
+        &mut self,
+    ) -> Result<(u64, SensorReading), Error<I2C::Error>> {
+        let measurement = self.get_measurement().await?;
+        let timestamp = match self.timestamps.monotonic {
+            Some(previous) => previous + measurement.timestamp.wrapping_sub(previous as u16) as u64,
+            None => measurement.timestamp as u64,
+        };
+        self.timestamps.monotonic = Some(timestamp);
+        Ok((timestamp, measurement))
+    }
+
+    /// Get a measurement that was not returned by a previous call to this method.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: new measurement.
+    /// * `Err(Error::Timeout)`: if no new measurement was produced within 3 frame periods.
+    /// * `Err(Error::InvalidState)`: if the power mode register contains an invalid value.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * In continuous ranging mode, reading faster than the framerate returns the same frame
+    ///   again. When the measurement has the same timestamp as the one previously returned,
+    ///   this method waits for one frame period of [`TFLuna::get_effective_framerate()`]
+    ///   and reads again, up to 3 times.
+    /// * When the device has no continuous output (framerate of 0 or ultra-low power mode),
+    ///   no new frame can appear and `Err(Error::Timeout)` is returned without waiting.
+
+    #[bisync]
+    pub async fn get_measurement_fresh(&mut self) -> Result<SensorReading, Error<I2C::Error>> {
+        let mut measurement = self.get_measurement().await?;
+        if self.timestamps.fresh == Some(measurement.timestamp) {
+            let framerate = self.get_effective_framerate().await?;
+            if framerate == 0 {
+                return Err(Error::Timeout);
+            }
+            let frame_period_ms = 1000u32.div_ceil(framerate as u32);
+            let mut frames = 0;
+            while self.timestamps.fresh == Some(measurement.timestamp) {
+                if frames == constants::FRESH_MEASUREMENT_MAX_FRAMES {
+                    return Err(Error::Timeout);
+                }
+                self.delay.delay_ms(frame_period_ms).await;
+                measurement = self.get_measurement().await?;
+                frames += 1;
+            }
+        }
+        self.timestamps.fresh = Some(measurement.timestamp);
+        Ok(measurement)
     }
 
     /// Trigger a single measurement (only effective in [`RangingMode::Trigger`]).
     ///
     /// # Returns
     /// * `Ok(())`: if trigger was set successfully.
+    /// * `Err(Error::InvalidState)`: if the device is known to be in [`RangingMode::Continuous`].
     /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
     ///
     /// # Notes
     /// * Only works when device is in [`RangingMode::Trigger`].
     /// * Initiates immediate measurement in trigger mode.
+    /// * Use [`TFLuna::read_triggered()`] to collect the measurement once it is ready.
+    /// * Returns `Err(Error::InvalidState)` without sending anything if the ranging mode last
+    ///   set or read through the driver is [`RangingMode::Continuous`]. The ranging mode is
+    ///   not read to check it, so the trigger is sent when the mode is unknown, e.g. right
+    ///   after creating the controller or rebooting the device.
 
     #[bisync]
     pub async fn trigger_measurement(&mut self) -> Result<(), Error<I2C::Error>> {
+        if self.ranging_mode == Some(RangingMode::Continuous) {
+            return Err(Error::InvalidState);
+        }
         self.write_byte(Register::Trigger, constants::TRIGGER_COMMAND_VALUE)
             .await?;
         Ok(())
     }
+
+    /// Read the measurement produced by a previous call to [`TFLuna::trigger_measurement()`].
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: the triggered measurement.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Triggering and reading are separate so that several devices can be triggered
+    ///   back-to-back and read afterwards, which keeps their measurements time-synchronized.
+    /// * Wait at least one frame period (`1000 / framerate` milliseconds, i.e. 10ms at the
+    ///   default 100Hz) between the trigger and the read, otherwise the previous measurement
+    ///   is returned.
+
+    #[bisync]
+    pub async fn read_triggered(&mut self) -> Result<SensorReading, Error<I2C::Error>> {
+        self.get_measurement().await
+    }
+
+    /// Wait in milliseconds between a trigger and the read of its measurement, i.e. one frame period.
+    ///
+    /// The frame period of the default framerate is used if the framerate is 0.
+    #[bisync]
+    async fn trigger_settle_ms(&mut self) -> Result<u32, Error<I2C::Error>> {
+        let framerate = match self.get_framerate().await? {
+            0 => constants::DEFAULT_FRAMERATE,
+            framerate => framerate,
+        };
+        Ok(1000u32.div_ceil(framerate as u32))
+    }
+
+    /// Switch to [`RangingMode::Trigger`], trigger a measurement and read it.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: measurement produced by the trigger.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Reading right after switching to trigger mode returns the last frame measured
+    ///   in continuous mode. This method avoids that stale frame by waiting 100ms for the
+    ///   mode switch to take effect, triggering, and waiting one frame period before reading.
+    /// * The framerate is read to compute the frame period, see [`TFLuna::trigger_burst()`].
+
+    #[bisync]
+    pub async fn enter_trigger_mode_and_fire(
+        &mut self,
+    ) -> Result<SensorReading, Error<I2C::Error>> {
+        self.set_ranging_mode(RangingMode::Trigger).await?;
+        log_transition!(
+            "Waiting {}ms for the ranging mode to take effect",
+            constants::RANGING_MODE_SWITCH_DELAY_MS
+        );
+        self.delay
+            .delay_ms(constants::RANGING_MODE_SWITCH_DELAY_MS)
+            .await;
+        let settle_ms = self.trigger_settle_ms().await?;
+        self.trigger_measurement().await?;
+        self.delay.delay_ms(settle_ms).await;
+        self.read_triggered().await
+    }
+
+    /// Trigger a measurement and read it after a fixed wait.
+    ///
+    /// # Arguments
+    /// * `settle_ms`: wait in milliseconds between the trigger and the read, at least one
+    ///   frame period (`1000 / framerate` milliseconds, i.e. 10ms at the default 100Hz).
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: measurement produced by the trigger.
+    /// * `Err(Error::InvalidState)`: if the device is not in [`RangingMode::Trigger`],
+    ///   nothing is triggered.
+    /// * `Err(Error::InvalidData)`: if the ranging mode register contains an invalid value.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The ranging mode is read before triggering. Use [`TFLuna::enter_trigger_mode_and_fire()`]
+    ///   to switch to trigger mode first.
+
+    #[bisync]
+    pub async fn trigger_and_measure(
+        &mut self,
+        settle_ms: u32,
+    ) -> Result<SensorReading, Error<I2C::Error>> {
+        if self.get_ranging_mode().await? != RangingMode::Trigger {
+            return Err(Error::InvalidState);
+        }
+        self.trigger_measurement().await?;
+        self.delay.delay_ms(settle_ms).await;
+        self.read_triggered().await
+    }
+
+    /// Trigger and read several measurements in quick succession.
+    ///
+    /// # Arguments
+    /// * `count`: number of measurements to capture.
+    /// * `buf`: buffer the measurements are written to.
+    ///
+    /// # Returns
+    /// * `Ok(usize)`: number of measurements captured, i.e. the smallest of `count` and `buf.len()`.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error, the measurements captured
+    ///   so far are left in `buf`.
+    ///
+    /// # Notes
+    /// * The device should be in [`RangingMode::Trigger`], see [`TFLuna::set_ranging_mode()`].
+    /// * The framerate is read once before the burst to compute the wait between each trigger
+    ///   and the following read, i.e. one frame period (`1000 / framerate` milliseconds).
+    ///   If the framerate is 0, the frame period of the default 100Hz framerate is used.
+
+    #[bisync]
+    pub async fn trigger_burst(
+        &mut self,
+        count: usize,
+        buf: &mut [SensorReading],
+    ) -> Result<usize, Error<I2C::Error>> {
+        let count = count.min(buf.len());
+        if count == 0 {
+            return Ok(0);
+        }
+        let settle_ms = self.trigger_settle_ms().await?;
+        for measurement in buf.iter_mut().take(count) {
+            self.trigger_measurement().await?;
+            self.delay.delay_ms(settle_ms).await;
+            *measurement = self.read_triggered().await?;
+        }
+        Ok(count)
+    }
+
+    /// Get the average of several consecutive measurements.
+    ///
+    /// # Arguments
+    /// * `samples`: number of measurements to average, at least 1.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: measurement with the mean distance, signal strength and temperature,
+    ///   the timestamp of the last measurement and the bitwise OR of all error codes.
+    /// * `Err(Error::InvalidParameter)`: if `samples` is 0.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The measurements are read 10ms apart, i.e. one frame period at the default framerate
+    ///   of 100Hz. At lower framerates, some measurements are averaged more than once.
+    /// * Unreliable measurements are averaged as well, check [`SensorReading::is_valid()`]
+    ///   on the result or use [`TFLuna::get_robust_distance()`] to skip them.
+
+    #[bisync]
+    pub async fn get_averaged_measurement(
+        &mut self,
+        samples: u8,
+    ) -> Result<SensorReading, Error<I2C::Error>> {
+        if samples == 0 {
+            return Err(Error::InvalidParameter);
+        }
+        let mut distance: u32 = 0;
+        let mut signal_strength: u32 = 0;
+        let mut temperature: f32 = 0.0;
+        let mut error = 0;
+        let mut timestamp = 0;
+        for i in 0..samples {
+            if i > 0 {
+                self.delay
+                    .delay_ms(constants::FRESH_MEASUREMENT_POLL_INTERVAL_MS)
+                    .await;
+            }
+            let measurement = self.get_measurement().await?;
+            distance += measurement.distance as u32;
+            signal_strength += measurement.signal_strength as u32;
+            temperature += measurement.temperature;
+            error |= measurement.error;
+            timestamp = measurement.timestamp;
+        }
+        Ok(SensorReading {
+            distance: (distance / samples as u32) as u16,
+            signal_strength: (signal_strength / samples as u32) as u16,
+            temperature: temperature / samples as f32,
+            timestamp,
+            error,
+        })
+    }
+
+    /// Get the median distance of several consecutive measurements.
+    ///
+    /// # Arguments
+    /// * `samples`: number of measurements to take, between 1 and 9.
+    ///
+    /// # Returns
+    /// * `Ok(u16)`: median distance of the measurements.
+    /// * `Err(Error::InvalidParameter)`: if `samples` is 0 or above 9.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The measurements are read 10ms apart, as in [`TFLuna::get_averaged_measurement()`].
+    /// * Unlike the mean, the median isn't affected by a single spike, e.g. to avoid false
+    ///   detections when comparing the distance to a threshold.
+    /// * Unreliable measurements are included, use [`TFLuna::get_robust_distance()`]
+    ///   to skip them.
+    /// * With an even number of samples, the lower of the two middle distances is returned.
+
+    #[bisync]
+    pub async fn get_median_distance(&mut self, samples: u8) -> Result<u16, Error<I2C::Error>> {
+        if samples == 0 || samples > constants::MEDIAN_DISTANCE_MAX_SAMPLES {
+            return Err(Error::InvalidParameter);
+        }
+        let samples = samples as usize;
+        let mut distances = [0; constants::MEDIAN_DISTANCE_MAX_SAMPLES as usize];
+        for (count, distance) in distances[..samples].iter_mut().enumerate() {
+            if count > 0 {
+                self.delay
+                    .delay_ms(constants::FRESH_MEASUREMENT_POLL_INTERVAL_MS)
+                    .await;
+            }
+            *distance = self.get_measurement().await?.distance;
+        }
+        Ok(median(&mut distances[..samples]))
+    }
+
+    /// Get the median distance of a short burst of triggered measurements.
+    ///
+    /// # Arguments
+    /// * `samples`: number of measurements to take, between 1 and 16.
+    ///
+    /// # Returns
+    /// * `Ok(u16)`: median distance of the reliable measurements.
+    /// * `Err(Error::InvalidParameter)`: if `samples` is 0 or above 16.
+    /// * `Err(Error::NoValidSamples)`: if none of the measurements was reliable.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The measurements are taken with [`TFLuna::trigger_burst()`], so the device
+    ///   should be in [`RangingMode::Trigger`].
+    /// * Measurements with an error or a signal strength below 100 or saturated (65535)
+    ///   are skipped. The median rejects the occasional spikes of the device.
+    /// * With an even number of reliable measurements, the lower of the two middle distances
+    ///   is returned.
+
+    #[bisync]
+    pub async fn get_robust_distance(&mut self, samples: u8) -> Result<u16, Error<I2C::Error>> {
+        if samples == 0 || samples > constants::ROBUST_DISTANCE_MAX_SAMPLES {
+            return Err(Error::InvalidParameter);
+        }
+        let mut measurements = [SensorReading {
+            distance: 0,
+            signal_strength: 0,
+            temperature: 0.0,
+            timestamp: 0,
+            error: 0,
+        }; constants::ROBUST_DISTANCE_MAX_SAMPLES as usize];
+        let count = self
+            .trigger_burst(samples as usize, &mut measurements)
+            .await?;
+        let mut distances = [0; constants::ROBUST_DISTANCE_MAX_SAMPLES as usize];
+        let mut valid = 0;
+        for measurement in measurements[..count].iter().filter(|m| m.is_valid()) {
+            distances[valid] = measurement.distance;
+            valid += 1;
+        }
+        if valid == 0 {
+            return Err(Error::NoValidSamples);
+        }
+        Ok(median(&mut distances[..valid]))
+    }
+
+    /// Read measurements until the distance is stable.
+    ///
+    /// # Arguments
+    /// * `tolerance_cm`: maximum spread, in centimeters, between the distances of the window.
+    /// * `samples`: number of consecutive reliable measurements in the window, between 1 and 16.
+    /// * `timeout_ms`: time in milliseconds after which to give up.
+    ///
+    /// # Returns
+    /// * `Ok(u16)`: average distance of the window once it is stable.
+    /// * `Err(Error::InvalidParameter)`: if `samples` is 0 or above 16.
+    /// * `Err(Error::Timeout)`: if the distance didn't stabilize within `timeout_ms`.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The device is polled every 10ms and only new measurements, i.e. with a new timestamp,
+    ///   are added to the window, so measurements should be enabled in continuous ranging mode.
+    /// * A measurement that is not reliable (error, signal strength below 100 or saturated)
+    ///   empties the window.
+
+    #[bisync]
+    pub async fn wait_for_stable(
+        &mut self,
+        tolerance_cm: u16,
+        samples: u8,
+        timeout_ms: u32,
+    ) -> Result<u16, Error<I2C::Error>> {
+        if samples == 0 || samples > constants::STABILITY_WINDOW_MAX_SAMPLES {
+            return Err(Error::InvalidParameter);
+        }
+        let samples = samples as usize;
+        let mut window = [0; constants::STABILITY_WINDOW_MAX_SAMPLES as usize];
+        let mut count = 0;
+        let mut last_timestamp = None;
+        let mut elapsed_ms = 0;
+        loop {
+            let measurement = self.get_measurement().await?;
+            if last_timestamp != Some(measurement.timestamp) {
+                last_timestamp = Some(measurement.timestamp);
+                if measurement.is_valid() {
+                    window[count % samples] = measurement.distance;
+                    count += 1;
+                } else {
+                    count = 0;
+                }
+                if count >= samples {
+                    let window = &window[..samples];
+                    let minimum = window.iter().min().copied().unwrap_or_default();
+                    let maximum = window.iter().max().copied().unwrap_or_default();
+                    if maximum - minimum <= tolerance_cm {
+                        let sum: u32 = window.iter().map(|&distance| distance as u32).sum();
+                        return Ok((sum / samples as u32) as u16);
+                    }
+                }
+            }
+            if elapsed_ms >= timeout_ms {
+                return Err(Error::Timeout);
+            }
+            self.delay
+                .delay_ms(constants::FRESH_MEASUREMENT_POLL_INTERVAL_MS)
+                .await;
+            elapsed_ms += constants::FRESH_MEASUREMENT_POLL_INTERVAL_MS;
+        }
+    }
+
+    /// Create a stream of measurements taken at a fixed interval.
+    ///
+    /// # Arguments
+    /// * `interval_ms`: delay in milliseconds between two consecutive measurements.
+    ///
+    /// # Returns
+    /// * `MeasurementStream`: stream borrowing this instance, see [`MeasurementStream::next_measurement()`].
+    ///   In the blocking interface, the stream is also an [`Iterator`].
+    pub fn stream(&mut self, interval_ms: u32) -> MeasurementStream<'_, I2C, D, P> {
+        MeasurementStream {
+            tfluna: self,
+            interval_ms,
+            started: false,
+        }
+    }
+}
+
+impl<I2C, D, P> TFLuna<I2C, D, P>
+where
+    I2C: I2cTrait<SevenBitAddress>,
+    D: DelayNs,
+    P: InputPin,
+{
+    /// Check whether a new measurement is available using the data-ready pin.
+    ///
+    /// # Returns
+    /// * `Ok(true)`: if the data-ready signal is asserted (pin is high).
+    /// * `Ok(false)`: if no new measurement is available (pin is low).
+    /// * `Err(Error::Pin)`: if the pin could not be read.
+    ///
+    /// # Notes
+    /// * Only available once the pin, connected to the TF-Luna's pin 6 (multiplexing output),
+    ///   was set with [`TFLuna::with_data_ready()`]. Nothing is sent to the device.
+    /// * The TF-Luna does not expose a data-ready flag in its I2C register map,
+    ///   the only way to know whether a new measurement is available is through pin 6.
+    /// * In I2C mode, pin 6 goes high when a new measurement is ready and goes low
+    ///   once the measurement has been read.
+    /// * Boards that don't route pin 6 to the MCU cannot use this method and should instead
+    ///   compare consecutive [`SensorReading::timestamp`] values.
+    /// * Pin 6 cannot be disabled, no register of the I2C register map controls it and it is
+    ///   not tied to the ranging mode. On boards that reuse the MCU pin, leave pin 6 unconnected
+    ///   or isolate it, e.g. with a series resistor.
+    pub fn is_data_ready(&mut self) -> Result<bool, Error<I2C::Error>> {
+        self.data_ready.is_high().map_err(|_| Error::Pin)
+    }
+
+    /// Get a measurement only if a new one is available, using the data-ready pin.
+    ///
+    /// # Returns
+    /// * `Ok(Some(SensorReading))`: if a new measurement was available and read.
+    /// * `Ok(None)`: if no new measurement is available, nothing is read from the device.
+    /// * `Err(Error::Pin)`: if the pin could not be read.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Only available once the pin was set with [`TFLuna::with_data_ready()`].
+    /// * See [`TFLuna::is_data_ready()`] for the behavior of pin 6.
+
+    #[bisync]
+    pub async fn measure_if_ready(&mut self) -> Result<Option<SensorReading>, Error<I2C::Error>> {
+        if self.is_data_ready()? {
+            self.get_measurement().await.map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+    /// Get a measurement without blocking, for super-loop firmware.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: if a new measurement was available and read.
+    /// * `Err(nb::Error::WouldBlock)`: if no new measurement is available yet.
+    /// * `Err(nb::Error::Other(Error::Pin))`: if the pin could not be read.
+    /// * `Err(nb::Error::Other(Error::I2c(I2CError)))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Only available once the pin was set with [`TFLuna::with_data_ready()`].
+    /// * Only the data-ready pin is polled until it is asserted, see [`TFLuna::is_data_ready()`]
+    ///   for the behavior of pin 6.
+    /// * No state is kept between calls. Reading the measurement deasserts the pin, so calling
+    ///   this again after it returned a measurement returns `WouldBlock` until the next frame.
+    #[only_sync]
+    #[cfg(feature = "nb")]
+    pub fn try_get_measurement(&mut self) -> nb::Result<SensorReading, Error<I2C::Error>> {
+        if self.is_data_ready()? {
+            self.get_measurement().map_err(nb::Error::Other)
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+}
+
+/// Find the TF-Luna devices on the bus.
+///
+/// Reads the signature register at every address from 0x08 to 0x77 and keeps the addresses
+/// that answered with the 'L' 'U' 'N' 'A' signature, so other devices on the bus are ignored.
+///
+/// # Arguments
+/// * `i2c`: I2C bus to scan.
+///
+/// # Returns
+/// * `Ok(AddressSet)`: addresses of the devices found, possibly none.
+/// * `Err(Error::I2c(I2CError))`: if there was an I2C error other than a NACK.
+///
+/// # Notes
+/// * Devices in ultra-low power mode don't answer and are not found.
+/// * Useful at boot to find a device whose address was changed and forgotten.
+
+#[bisync]
+pub async fn scan_addresses<I2C>(i2c: &mut I2C) -> Result<AddressSet, Error<I2C::Error>>
+where
+    I2C: I2cTrait<SevenBitAddress>,
+{
+    let mut found = AddressSet::default();
+    for address in constants::SLAVE_ADDRESS_MINIMUM_VALUE..=constants::SLAVE_ADDRESS_MAXIMUM_VALUE {
+        let mut signature = [0; 4];
+        match i2c
+            .write_read(address, &[Register::Signature as u8], &mut signature)
+            .await
+        {
+            Ok(()) if signature == SIGNATURE => found.insert(Address(address)),
+            Ok(()) => {}
+            Err(e) if matches!(e.kind(), ErrorKind::NoAcknowledge(_)) => {}
+            Err(e) => return Err(Error::I2c(e)),
+        }
+    }
+    Ok(found)
+}
+
+/// Builder that creates a controller and configures the device in one go.
+///
+/// Created with [`TFLuna::builder()`]. Each setter validates its value, so invalid
+/// settings are rejected before any data is sent to the device.
+///
+/// ```rust
+/// let mut tfluna = TFLuna::builder()
+///     .framerate(50)?
+///     .ranging_mode(RangingMode::Continuous)
+///     .maximum_distance(300)?
+///     .save_settings(true)
+///     .build(i2c, Address::default(), Delay::new())?;
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TFLunaBuilder<I2C, D> {
+    /// Framerate to set
+    framerate: Option<u16>,
+    /// Ranging mode to set
+    ranging_mode: Option<RangingMode>,
+    /// Signal strength threshold to set
+    signal_strength_threshold: Option<u16>,
+    /// Minimum distance to set
+    minimum_distance: Option<u16>,
+    /// Maximum distance to set
+    maximum_distance: Option<u16>,
+    /// Whether the settings are saved once applied
+    save_settings: bool,
+    /// Types of the I2C bus and delay passed to `build`
+    _marker: PhantomData<fn() -> (I2C, D)>,
+}
+
+impl<I2C, D> TFLunaBuilder<I2C, D>
+where
+    I2C: I2cTrait<SevenBitAddress>,
+    D: DelayNs,
+{
+    fn new() -> Self {
+        Self {
+            framerate: None,
+            ranging_mode: None,
+            signal_strength_threshold: None,
+            minimum_distance: None,
+            maximum_distance: None,
+            save_settings: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the measurement framerate in Hz.
+    ///
+    /// # Returns
+    /// * `Ok(Self)`: if the framerate is valid.
+    /// * `Err(Error::InvalidParameter)`: if the framerate is invalid, see [`TFLuna::set_framerate()`].
+    pub fn framerate(mut self, value: u16) -> Result<Self, Error<I2C::Error>> {
+        if value != 0 && !FRAMERATE_OPTIONS.contains(&value) {
+            return Err(Error::InvalidParameter);
+        }
+        self.framerate = Some(value);
+        Ok(self)
+    }
+
+    /// Set the ranging mode.
+    pub fn ranging_mode(mut self, mode: RangingMode) -> Self {
+        self.ranging_mode = Some(mode);
+        self
+    }
+
+    /// Set the signal strength threshold, see [`TFLuna::set_signal_strength_threshold()`].
+    pub fn signal_strength_threshold(mut self, value: u16) -> Self {
+        self.signal_strength_threshold = Some(value);
+        self
+    }
+
+    /// Set the minimum valid distance.
+    ///
+    /// # Returns
+    /// * `Ok(Self)`: if the distance is valid.
+    /// * `Err(Error::InvalidParameter)`: if the maximum distance is already set and is below `value`.
+    pub fn minimum_distance(mut self, value: u16) -> Result<Self, Error<I2C::Error>> {
+        if self.maximum_distance.is_some_and(|maximum| value > maximum) {
+            return Err(Error::InvalidParameter);
+        }
+        self.minimum_distance = Some(value);
+        Ok(self)
+    }
+
+    /// Set the maximum valid distance.
+    ///
+    /// # Returns
+    /// * `Ok(Self)`: if the distance is valid.
+    /// * `Err(Error::InvalidParameter)`: if the minimum distance is already set and is above `value`.
+    pub fn maximum_distance(mut self, value: u16) -> Result<Self, Error<I2C::Error>> {
+        if self.minimum_distance.is_some_and(|minimum| value < minimum) {
+            return Err(Error::InvalidParameter);
+        }
+        self.maximum_distance = Some(value);
+        Ok(self)
+    }
+
+    /// Set whether the settings are saved once applied, off by default.
+    pub fn save_settings(mut self, save: bool) -> Self {
+        self.save_settings = save;
+        self
+    }
+
+    /// Create the controller and apply the settings.
+    ///
+    /// # Arguments
+    /// * `i2c`: I2C bus the device is connected to.
+    /// * `address`: I2C slave address of the device.
+    /// * `delay`: delay implementation.
+    ///
+    /// # Returns
+    /// * `Ok(TFLuna)`: configured controller.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error, the first failed write aborts the build.
+    ///
+    /// # Notes
+    /// * The settings are written in this order: framerate, ranging mode, signal strength
+    ///   threshold, minimum distance, maximum distance. Settings that were not set are left untouched.
+    /// * The settings are saved last when requested with [`TFLunaBuilder::save_settings()`].
+
+    #[bisync]
+    pub async fn build(
+        self,
+        i2c: I2C,
+        address: Address,
+        delay: D,
+    ) -> Result<TFLuna<I2C, D>, Error<I2C::Error>> {
+        let mut tfluna = TFLuna::new(i2c, address, delay)?;
+        if let Some(framerate) = self.framerate {
+            tfluna.set_framerate(framerate).await?;
+        }
+        if let Some(mode) = self.ranging_mode {
+            tfluna.set_ranging_mode(mode).await?;
+        }
+        if let Some(threshold) = self.signal_strength_threshold {
+            tfluna.set_signal_strength_threshold(threshold).await?;
+        }
+        if let Some(distance) = self.minimum_distance {
+            tfluna.set_minimum_distance(distance).await?;
+        }
+        if let Some(distance) = self.maximum_distance {
+            tfluna.set_maximum_distance(distance).await?;
+        }
+        if self.save_settings {
+            tfluna.save_settings().await?;
+        }
+        Ok(tfluna)
+    }
+}
+
+/// Stream of measurements taken at a fixed interval.
+///
+/// Created with [`TFLuna::stream()`].
+///
+/// # Cancellation
+///
+/// Dropping the future returned by [`MeasurementStream::next_measurement()`] while it is reading
+/// from the device (e.g. when it loses a `select!`) is safe: the interruption is recorded
+/// in the [`TFLuna`] instance, even if the stream itself is dropped as well, and the next
+/// measurement read from any stream created from that instance starts with a full-frame
+/// read that is discarded. This guarantees that a cancelled read never desynchronizes
+/// subsequent measurements.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeasurementStream<'a, I2C: I2cTrait<SevenBitAddress>, D: DelayNs, P = NoPin> {
+    /// Device the measurements are read from
+    tfluna: &'a mut TFLuna<I2C, D, P>,
+    /// Delay in milliseconds between two consecutive measurements
+    interval_ms: u32,
+    /// Whether a measurement was already returned
+    started: bool,
+}
+
+impl<I2C, D, P> MeasurementStream<'_, I2C, D, P>
+where
+    I2C: I2cTrait<SevenBitAddress>,
+    D: DelayNs,
+{
+    /// Wait for the configured interval and read the next measurement.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: next measurement.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * The first measurement is read without waiting.
+    #[bisync]
+    pub async fn next_measurement(&mut self) -> Result<SensorReading, Error<I2C::Error>> {
+        if self.started {
+            self.tfluna.delay.delay_ms(self.interval_ms).await;
+        }
+        if self.tfluna.stream_read_interrupted {
+            // A previous read was cancelled, resynchronize with a full-frame read
+            self.tfluna.get_measurement().await?;
+        }
+        self.tfluna.stream_read_interrupted = true;
+        let measurement = self.tfluna.get_measurement().await;
+        self.tfluna.stream_read_interrupted = false;
+        self.started = true;
+        measurement
+    }
+
+    /// Check whether the next measurement will be preceded by a resynchronization read.
+    pub fn is_resync_pending(&self) -> bool {
+        self.tfluna.stream_read_interrupted
+    }
+}
+
+/// Blocking streams are iterators over the results of [`MeasurementStream::next_measurement()`].
+///
+/// The iterator never ends, use e.g. [`Iterator::take()`] to read a given number of measurements.
+/// Errors are returned as `Err` items and iterating can continue afterwards.
+#[only_sync]
+impl<I2C, D, P> Iterator for MeasurementStream<'_, I2C, D, P>
+where
+    I2C: I2cTrait<SevenBitAddress>,
+    D: DelayNs,
+{
+    type Item = Result<SensorReading, Error<I2C::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_measurement())
+    }
+}
+
+impl<'a, I2C, D, P> MeasurementStream<'a, I2C, D, P>
+where
+    I2C: I2cTrait<SevenBitAddress>,
+    D: DelayNs,
+{
+    /// Only keep every `factor`-th measurement of the stream.
+    ///
+    /// # Arguments
+    /// * `factor`: number of measurements read for each returned measurement, `0` is treated as `1`.
+    ///
+    /// # Returns
+    /// * `Decimator`: stream adapter, see [`Decimator::next_measurement()`].
+    pub fn decimate(self, factor: u8) -> Decimator<'a, I2C, D, P> {
+        Decimator {
+            stream: self,
+            factor: factor.max(1),
+        }
+    }
+
+    /// Keep running statistics of the distances over the last `N` measurements of the stream.
+    ///
+    /// # Returns
+    /// * `Statistics`: stream adapter, see [`Statistics::next_measurement()`] and [`Statistics::stats()`].
+    pub fn statistics<const N: usize>(self) -> Statistics<'a, I2C, D, N, P> {
+        Statistics {
+            stream: self,
+            window: [0; N],
+            next: 0,
+            count: 0,
+            sum: 0,
+            sum_of_squares: 0,
+        }
+    }
+}
+
+/// Stream adapter that only keeps every N-th measurement.
+///
+/// Created with [`MeasurementStream::decimate()`]. Useful to reduce the processing load
+/// without lowering the device framerate, e.g. to keep the device's internal averaging.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Decimator<'a, I2C: I2cTrait<SevenBitAddress>, D: DelayNs, P = NoPin> {
+    /// Underlying stream
+    stream: MeasurementStream<'a, I2C, D, P>,
+    /// Number of measurements read for each returned measurement
+    factor: u8,
+}
+
+impl<I2C, D, P> Decimator<'_, I2C, D, P>
+where
+    I2C: I2cTrait<SevenBitAddress>,
+    D: DelayNs,
+{
+    /// Read `factor` measurements from the stream and return the last one.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: last measurement read.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error, the remaining
+    ///   measurements are not read.
+    #[bisync]
+    pub async fn next_measurement(&mut self) -> Result<SensorReading, Error<I2C::Error>> {
+        for _ in 1..self.factor {
+            self.stream.next_measurement().await?;
+        }
+        self.stream.next_measurement().await
+    }
+
+    /// Get the decimation factor.
+    pub fn factor(&self) -> u8 {
+        self.factor
+    }
+}
+
+/// Stream adapter that keeps statistics of the distances over a fixed window of measurements.
+///
+/// Created with [`MeasurementStream::statistics()`]. Only the last `N` distances are kept,
+/// along with their sum and sum of squares, which are updated as distances enter and leave
+/// the window.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Statistics<'a, I2C: I2cTrait<SevenBitAddress>, D: DelayNs, const N: usize, P = NoPin> {
+    /// Underlying stream
+    stream: MeasurementStream<'a, I2C, D, P>,
+    /// Last distances, used as a ring buffer
+    window: [u16; N],
+    /// Index in the window of the next distance
+    next: usize,
+    /// Number of distances in the window
+    count: usize,
+    /// Sum of the distances in the window
+    sum: u64,
+    /// Sum of the squared distances in the window
+    sum_of_squares: u64,
+}
+
+impl<I2C, D, const N: usize, P> Statistics<'_, I2C, D, N, P>
+where
+    I2C: I2cTrait<SevenBitAddress>,
+    D: DelayNs,
+{
+    /// Read the next measurement from the stream and add its distance to the window.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: measurement read.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * Measurements that are not reliable (error, signal strength below 100 or saturated)
+    ///   are returned but not added to the window.
+    #[bisync]
+    pub async fn next_measurement(&mut self) -> Result<SensorReading, Error<I2C::Error>> {
+        let measurement = self.stream.next_measurement().await?;
+        if N > 0 && measurement.is_valid() {
+            if self.count == N {
+                let evicted = self.window[self.next] as u64;
+                self.sum -= evicted;
+                self.sum_of_squares -= evicted * evicted;
+            } else {
+                self.count += 1;
+            }
+            let distance = measurement.distance as u64;
+            self.window[self.next] = measurement.distance;
+            self.sum += distance;
+            self.sum_of_squares += distance * distance;
+            self.next = (self.next + 1) % N;
+        }
+        Ok(measurement)
+    }
+
+    /// Get the statistics of the distances in the window.
+    ///
+    /// # Returns
+    /// * `Some(DistanceStatistics)`: statistics of the last `N` reliable distances,
+    ///   or fewer until the window is full.
+    /// * `None`: if no reliable distance was read yet.
+    pub fn stats(&self) -> Option<DistanceStatistics> {
+        let distances = &self.window[..self.count];
+        Some(DistanceStatistics {
+            count: self.count,
+            minimum: *distances.iter().min()?,
+            maximum: *distances.iter().max()?,
+            sum: self.sum,
+            sum_of_squares: self.sum_of_squares,
+        })
+    }
+}
+
+/// Get the median of a non-empty slice, sorting it in place with an insertion sort.
+///
+/// With an even number of values, the lower of the two middle values is returned.
+fn median(values: &mut [u16]) -> u16 {
+    for i in 1..values.len() {
+        let mut j = i;
+        while j > 0 && values[j - 1] > values[j] {
+            values.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+    values[(values.len() - 1) / 2]
 }
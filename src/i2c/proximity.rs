@@ -0,0 +1,86 @@
+use crate::i2c::types::{Error, NoPin};
+
+use super::device::TFLuna;
+use super::{bisync, only_async, only_sync};
+
+#[only_sync]
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{I2c as I2cTrait, SevenBitAddress},
+};
+
+#[only_async]
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{I2c as I2cTrait, SevenBitAddress},
+};
+
+/// Simple proximity switch built on top of a [`TFLuna`].
+///
+/// The switch is considered tripped when a valid measurement reports
+/// a distance closer than the configured threshold.
+///
+/// # Example
+///
+/// ```rust
+/// let tfluna = TFLuna::new(i2c, Address::default(), Delay::new())?;
+/// // Trip when something is closer than 50cm
+/// let mut switch = ProximitySwitch::new(tfluna, 50);
+/// if switch.poll()? {
+///     // Something is close
+/// }
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ProximitySwitch<I2C: I2cTrait<SevenBitAddress>, D: DelayNs, P = NoPin> {
+    /// Underlying device
+    tfluna: TFLuna<I2C, D, P>,
+    /// Distance threshold in centimeters
+    threshold: u16,
+}
+
+impl<I2C, D, P> ProximitySwitch<I2C, D, P>
+where
+    I2C: I2cTrait<SevenBitAddress>,
+    D: DelayNs,
+{
+    /// Create a new proximity switch.
+    ///
+    /// # Arguments
+    /// * `tfluna`: device instance, measurements should already be enabled.
+    /// * `threshold`: distance in centimeters below which the switch trips.
+    pub fn new(tfluna: TFLuna<I2C, D, P>, threshold: u16) -> Self {
+        Self { tfluna, threshold }
+    }
+
+    /// Get the distance threshold in centimeters.
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Set the distance threshold in centimeters.
+    pub fn set_threshold(&mut self, threshold: u16) {
+        self.threshold = threshold;
+    }
+
+    /// Consume the switch and return the underlying device.
+    pub fn release(self) -> TFLuna<I2C, D, P> {
+        self.tfluna
+    }
+
+    /// Take a measurement and check whether something is within the threshold.
+    ///
+    /// # Returns
+    /// * `Ok(true)`: if the measurement is valid and its distance is below the threshold.
+    /// * `Ok(false)`: if nothing is within the threshold or the measurement is not reliable.
+    /// * `Err(Error::I2c(I2CError))`: if there was an I2C error.
+    ///
+    /// # Notes
+    /// * A measurement is reliable when the device reports no error and its signal strength
+    ///   is at least 100 without being saturated (65535).
+    #[bisync]
+    pub async fn poll(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let measurement = self.tfluna.get_measurement().await?;
+        Ok(measurement.is_valid() && measurement.distance < self.threshold)
+    }
+}
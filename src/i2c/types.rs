@@ -1,6 +1,10 @@
-use embedded_hal::i2c::Error as I2CErrorTrait;
+use embedded_hal::i2c::{Error as I2CErrorTrait, ErrorKind, NoAcknowledgeSource};
 
-use crate::i2c::constants::DEFAULT_SLAVE_ADDRESS;
+use crate::i2c::constants::{
+    CONFIGURED_DEFAULT_SLAVE_ADDRESS, POWER_MODE_SWITCH_DELAY_MS, ULTRA_LOW_POWER_REBOOT_DELAY_MS,
+    ULTRA_LOW_POWER_SAVE_DELAY_MS,
+};
+use crate::types::FirmwareVersion;
 
 /// I2C device address
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -8,9 +12,14 @@ use crate::i2c::constants::DEFAULT_SLAVE_ADDRESS;
 pub struct Address(pub(crate) u8);
 
 /// Default device address
+///
+/// This is `0x10` unless overridden at compile time with the `EMBEDDED_TFLUNA_DEFAULT_ADDRESS`
+/// environment variable, e.g. `EMBEDDED_TFLUNA_DEFAULT_ADDRESS=0x20 cargo build`.
+/// Decimal and `0x`-prefixed hexadecimal values between `0x08` and `0x77` are accepted,
+/// any other value fails the build.
 impl Default for Address {
     fn default() -> Self {
-        Address(DEFAULT_SLAVE_ADDRESS)
+        Address(CONFIGURED_DEFAULT_SLAVE_ADDRESS)
     }
 }
 
@@ -28,6 +37,171 @@ impl From<Address> for u8 {
     }
 }
 
+/// Slave address change that was written to the device but is not yet in effect.
+///
+/// Returned by `set_slave_address` and consumed by `commit_address_change`,
+/// which saves the settings, reboots the device and switches the driver to the new address.
+#[must_use = "the new address only takes effect after calling `commit_address_change`"]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AddressChangePending {
+    pub(crate) address: Address,
+}
+
+impl AddressChangePending {
+    /// Address the device will respond to once the change is committed.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+}
+
+/// Set of I2C slave addresses, returned by `scan_addresses`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AddressSet(pub(crate) u128);
+
+impl AddressSet {
+    /// Add an address to the set.
+    #[cfg(any(feature = "async", feature = "i2c-blocking"))]
+    pub(crate) fn insert(&mut self, address: Address) {
+        self.0 |= 1 << (address.0 & 0x7F);
+    }
+
+    /// Whether the set contains the address.
+    pub fn contains(&self, address: Address) -> bool {
+        self.0 & (1 << (address.0 & 0x7F)) != 0
+    }
+
+    /// Number of addresses in the set.
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the addresses in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Address> + '_ {
+        (0..=0x7F)
+            .map(Address)
+            .filter(|&address| self.contains(address))
+    }
+}
+
+/// How registers are read from the device.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadMode {
+    /// The register address is written and the data read in a single transaction,
+    /// with a repeated start condition in between.
+    #[default]
+    RepeatedStart,
+    /// The register address is written and the data read in two separate transactions,
+    /// with a stop condition in between, for bridges that don't handle repeated starts.
+    StopStart,
+}
+
+/// Retries applied by the driver to all operations.
+///
+/// The default is to not retry anything.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RetryConfig {
+    /// Number of retries of a read or write that was not acknowledged (NACK)
+    pub nack_retries: u8,
+    /// Delay in milliseconds before retrying a read or write that was not acknowledged
+    pub nack_delay_ms: u32,
+    /// Number of retries of a read or write that lost arbitration to another master
+    pub arbitration_loss_retries: u8,
+    /// Delay in milliseconds before retrying a read or write that lost arbitration
+    pub arbitration_loss_delay_ms: u32,
+    /// Number of retries of a read that returned an invalid value
+    pub invalid_data_retries: u8,
+    /// Delay in milliseconds before retrying a read that returned an invalid value
+    pub invalid_data_delay_ms: u32,
+}
+
+/// Delays applied by the driver when changing the power mode.
+///
+/// The default delays are the ones that work with the boards the driver was tested with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerModeTiming {
+    /// Delay in milliseconds after any power mode change, for the new mode to take effect.
+    /// Defaults to 100ms.
+    pub settle_ms: u32,
+    /// Delay in milliseconds between saving the ultra-low power mode setting and rebooting,
+    /// when entering or leaving ultra-low power mode. Defaults to 100ms.
+    pub save_ms: u32,
+    /// Delay in milliseconds after rebooting, when entering or leaving ultra-low power mode.
+    /// Defaults to 1000ms.
+    pub reboot_ms: u32,
+}
+
+impl Default for PowerModeTiming {
+    fn default() -> Self {
+        Self {
+            settle_ms: POWER_MODE_SWITCH_DELAY_MS,
+            save_ms: ULTRA_LOW_POWER_SAVE_DELAY_MS,
+            reboot_ms: ULTRA_LOW_POWER_REBOOT_DELAY_MS,
+        }
+    }
+}
+
+/// Retries left for a single read or write.
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+pub(crate) struct RetryBudget {
+    nack: u8,
+    arbitration_loss: u8,
+}
+
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+impl RetryBudget {
+    pub(crate) fn new(config: &RetryConfig) -> Self {
+        Self {
+            nack: config.nack_retries,
+            arbitration_loss: config.arbitration_loss_retries,
+        }
+    }
+
+    /// Consume a retry for the given error, returning the delay before retrying.
+    ///
+    /// Returns `None` if the error is not retryable or no retries are left.
+    pub(crate) fn next_delay_ms(&mut self, config: &RetryConfig, kind: ErrorKind) -> Option<u32> {
+        let (retries_left, delay_ms) = match kind {
+            ErrorKind::NoAcknowledge(_) => (&mut self.nack, config.nack_delay_ms),
+            ErrorKind::ArbitrationLoss => {
+                (&mut self.arbitration_loss, config.arbitration_loss_delay_ms)
+            }
+            _ => return None,
+        };
+        *retries_left = retries_left.checked_sub(1)?;
+        Some(delay_ms)
+    }
+}
+
+/// Data-ready pin of a controller that has none, see `TFLuna::with_data_ready`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NoPin;
+
+/// Timestamps remembered between calls by the methods that track new measurements.
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub(crate) struct TimestampTracker {
+    /// Timestamp read by the previous call to `ticks_since_last_read`
+    pub(crate) last_read: Option<u16>,
+    /// Timestamp read by the previous call to `take_new_data_flag`
+    pub(crate) new_data: Option<u16>,
+    /// Timestamp of the measurement last returned by `get_measurement_fresh`
+    pub(crate) fresh: Option<u16>,
+    /// Extended timestamp of the measurement last returned by `get_measurement_monotonic`
+    pub(crate) monotonic: Option<u64>,
+}
+
 /// I2C Error enum
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -38,6 +212,32 @@ pub enum Error<I2CError: I2CErrorTrait> {
     InvalidData(u8),
     /// Invalid parameter passed to method
     InvalidParameter,
+    /// Device did not respond in time
+    Timeout,
+    /// No device answered at the configured address
+    NotFound,
+    /// Device state does not match the expected provisioning
+    ProvisioningMismatch(ProvisioningCheck),
+    /// Device firmware is older than required
+    UnsupportedFirmware {
+        /// Firmware version of the device
+        found: FirmwareVersion,
+        /// Minimum required firmware version
+        required: FirmwareVersion,
+    },
+    /// A setting was written while the device is in ultra-low power mode, nothing was sent
+    UltraLowPowerActive,
+    /// The operation is not possible in the current mode of the device.
+    ///
+    /// Returned by:
+    /// * `trigger_measurement` and `trigger_and_measure` when the device is not in trigger mode.
+    /// * `get_power_mode` when the power saving mode register holds an unknown value, and so
+    ///   do the methods reading the power mode, e.g. `get_effective_framerate`.
+    InvalidState,
+    /// None of the measurements taken by a multi-sample read was reliable
+    NoValidSamples,
+    /// The data-ready pin could not be read
+    Pin,
     /// Other error
     Other,
 }
@@ -51,8 +251,161 @@ where
     }
 }
 
+impl<I2CError> core::fmt::Display for Error<I2CError>
+where
+    I2CError: I2CErrorTrait,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::I2c(e) => write!(f, "I2C error: {}", e.kind()),
+            Error::InvalidData(byte) => write!(f, "invalid data returned by device: {:#04x}", byte),
+            Error::InvalidParameter => write!(f, "invalid parameter"),
+            Error::Timeout => write!(f, "device did not respond in time"),
+            Error::NotFound => write!(f, "no device answered at the configured address"),
+            Error::ProvisioningMismatch(check) => {
+                write!(f, "device provisioning mismatch: {:?}", check)
+            }
+            Error::UnsupportedFirmware { found, required } => write!(
+                f,
+                "unsupported firmware version {}, {} or later is required",
+                found, required
+            ),
+            Error::UltraLowPowerActive => write!(f, "device is in ultra-low power mode"),
+            Error::InvalidState => write!(f, "operation not possible in the current device mode"),
+            Error::NoValidSamples => write!(f, "no reliable measurement among the samples"),
+            Error::Pin => write!(f, "data-ready pin could not be read"),
+            Error::Other => write!(f, "other error"),
+        }
+    }
+}
+
+/// The underlying I2C error is not returned as the source, since the `embedded-hal` error
+/// trait doesn't require it to implement [`core::error::Error`]. It is described by
+/// the [`core::fmt::Display`] implementation instead.
+impl<I2CError> core::error::Error for Error<I2CError> where I2CError: I2CErrorTrait {}
+
+impl<I2CError> Error<I2CError>
+where
+    I2CError: I2CErrorTrait,
+{
+    /// Get a suggestion of what to do to recover from the error.
+    ///
+    /// The hint only depends on the error itself, e.g. to show an actionable message to users.
+    pub fn recovery_hint(&self) -> RecoveryHint {
+        match self {
+            Error::I2c(e) => match e.kind() {
+                // The device doesn't acknowledge its address while in ultra-low power mode
+                ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => RecoveryHint::WakeDevice,
+                ErrorKind::NoAcknowledge(_) | ErrorKind::ArbitrationLoss | ErrorKind::Overrun => {
+                    RecoveryHint::RetryLater
+                }
+                ErrorKind::Bus => RecoveryHint::CheckWiring,
+                _ => RecoveryHint::None,
+            },
+            Error::Timeout | Error::InvalidData(_) | Error::NoValidSamples => {
+                RecoveryHint::RetryLater
+            }
+            Error::NotFound => RecoveryHint::CheckWiring,
+            Error::UltraLowPowerActive => RecoveryHint::WakeDevice,
+            Error::InvalidParameter
+            | Error::ProvisioningMismatch(_)
+            | Error::UnsupportedFirmware { .. }
+            | Error::InvalidState
+            | Error::Pin
+            | Error::Other => RecoveryHint::None,
+        }
+    }
+}
+
+/// Suggested action to recover from an [`Error`], see [`Error::recovery_hint()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RecoveryHint {
+    /// The error is transient (e.g. bus contention or a measurement not ready yet), retry later
+    RetryLater,
+    /// Nothing answered or the bus is faulty, check the wiring, pull-ups and address
+    CheckWiring,
+    /// The device may be asleep in ultra-low power mode, wake it up before retrying
+    WakeDevice,
+    /// Retrying won't help, the call or the device setup has to change
+    None,
+}
+
+/// Check performed by `verify_provisioning`, reported on the first mismatch.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ProvisioningCheck {
+    /// Driver address or slave address register
+    Address,
+    /// Device signature
+    Signature,
+    /// Firmware version
+    FirmwareVersion,
+    /// Framerate
+    Framerate,
+    /// Ranging mode
+    RangingMode,
+    /// Power mode
+    PowerMode,
+    /// Signal strength threshold
+    SignalStrengthThreshold,
+    /// Dummy distance
+    DummyDistance,
+    /// Minimum distance
+    MinimumDistance,
+    /// Maximum distance
+    MaximumDistance,
+}
+
+/// I2C transaction issued by the driver, passed to the trace callback.
+#[cfg(all(feature = "trace", any(feature = "async", feature = "i2c-blocking")))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TraceEvent<'a> {
+    /// Register read
+    Read {
+        /// Register the read started at
+        register: u8,
+        /// Bytes returned by the device
+        data: &'a [u8],
+    },
+    /// Register write
+    Write {
+        /// Register the write started at
+        register: u8,
+        /// Bytes written to the device, excluding the register address
+        data: &'a [u8],
+    },
+}
+
+/// Trace callback wrapper
+///
+/// Needed because function pointers don't implement `defmt::Format`.
+#[cfg(all(feature = "trace", any(feature = "async", feature = "i2c-blocking")))]
+#[derive(Clone, Copy)]
+pub(crate) struct Tracer(pub(crate) fn(&TraceEvent));
+
+#[cfg(all(feature = "trace", any(feature = "async", feature = "i2c-blocking")))]
+impl core::fmt::Debug for Tracer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Tracer")
+    }
+}
+
+#[cfg(all(
+    feature = "trace",
+    feature = "defmt",
+    any(feature = "async", feature = "i2c-blocking")
+))]
+impl defmt::Format for Tracer {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "Tracer")
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
 pub enum Register {
     /// Distance measurement low byte register - centimeters - Read-only
     Distance = 0x00,
@@ -60,10 +413,8 @@ pub enum Register {
     #[allow(dead_code)]
     SignalStrength = 0x02,
     /// Temperature measurement low byte register - 0.01°C units - Read-only
-    #[allow(dead_code)]
     Temperature = 0x04,
     /// Timestamp low byte register - device ticks - Read-only
-    #[allow(dead_code)]
     Timestamp = 0x06,
     /// Error low byte register - Read-only
     Error = 0x08,
@@ -102,3 +453,21 @@ pub enum Register {
     /// Signature lower byte register - 4-byte ASCII code - Read-only
     Signature = 0x3C,
 }
+
+#[cfg(any(feature = "async", feature = "i2c-blocking"))]
+impl Register {
+    /// Whether writing the register changes a setting that is lost on reboot unless saved.
+    pub(crate) fn is_persistent_setting(&self) -> bool {
+        matches!(
+            self,
+            Register::SlaveAddress
+                | Register::RangingMode
+                | Register::Framerate
+                | Register::PowerSavingMode
+                | Register::SignalStrengthThreshold
+                | Register::DummyDistance
+                | Register::MinimumDistance
+                | Register::MaximumDistance
+        )
+    }
+}
@@ -2,7 +2,16 @@
 #![no_std]
 #![deny(missing_docs)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod constants;
 pub mod i2c;
 mod types;
+#[cfg(feature = "uart")]
+pub mod uart;
 
-pub use types::{FirmwareVersion, PowerMode, RangingMode, SensorReading, SerialNumber, Signature};
+pub use types::{
+    Config, ConfigWord, DistanceStatistics, FirmwareVersion, LossPolicy, MeasurementView,
+    PowerMode, Preset, RangingMode, SensorReading, SerialNumber, Signature, Startup, TimestampBase,
+};
@@ -1,8 +1,19 @@
 //! Types of returned data from TF-Luna.
 
+use crate::constants::SIGNATURE;
+
+/// Minimum signal strength for a distance measurement to be considered reliable
+const MINIMUM_RELIABLE_SIGNAL_STRENGTH: u16 = 100;
+
+/// Maximum operating temperature in °C, as per the datasheet
+const MAXIMUM_OPERATING_TEMPERATURE: f32 = 60.0;
+
 /// Structure containing major, minor, and revision numbers.
-#[derive(Clone, Copy, Debug, PartialEq)]
+///
+/// Versions are ordered by major, then minor, then revision number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FirmwareVersion {
     /// Major version number
     pub major: u8,
@@ -12,20 +23,72 @@ pub struct FirmwareVersion {
     pub revision: u8,
 }
 
+impl core::fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.revision)
+    }
+}
+
 /// Structure containing the serial number of the device.
+///
+/// The serial number is ASCII text, e.g. `T3300245010082`. It is displayed as such,
+/// with non-printable bytes replaced with `?`.
 #[derive(Clone, Copy, Debug, PartialEq)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SerialNumber(pub [u8; 14]);
 
+impl SerialNumber {
+    /// Get the serial number as a string slice.
+    ///
+    /// # Returns
+    /// * `Ok(&str)`: serial number.
+    /// * `Err(Utf8Error)`: if the bytes returned by the device are not valid UTF-8.
+    pub fn as_str(&self) -> Result<&str, core::str::Utf8Error> {
+        core::str::from_utf8(&self.0)
+    }
+}
+
+impl core::fmt::Display for SerialNumber {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for &byte in &self.0 {
+            let character = match byte {
+                0x20..=0x7E => byte as char,
+                _ => '?',
+            };
+            write!(f, "{}", character)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for SerialNumber {
+    fn format(&self, f: defmt::Formatter) {
+        match self.as_str() {
+            Ok(serial_number) => defmt::write!(f, "{=str}", serial_number),
+            Err(_) => defmt::write!(f, "{=[u8]}", self.0),
+        }
+    }
+}
+
 /// ASCII signature of the device.
 ///
 /// The TF-Luna's signature is: 'L', 'U', 'N', 'A'
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Signature(pub [u8; 4]);
 
+impl Signature {
+    /// Whether this is the TF-Luna's signature, 'L', 'U', 'N', 'A'.
+    pub fn is_valid(&self) -> bool {
+        self.0 == SIGNATURE
+    }
+}
+
 /// Ranging modes of the device.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RangingMode {
     /// In Continuous ranging mode, the TF-Luna will keep tracking
     /// the distance at a 500hz frequency, but as the configured
@@ -38,8 +101,9 @@ pub enum RangingMode {
 }
 
 /// Enum containing the different power modes of the TF-Luna
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PowerMode {
     /// Normal power mode with largest power consumption
     ///
@@ -51,9 +115,269 @@ pub enum PowerMode {
     UltraLow,
 }
 
+/// How the device settings are initialized when starting it.
+///
+/// Settings changed with the setters are kept across reboots once saved,
+/// so the choice depends on whether the device was provisioned beforehand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Startup {
+    /// Restore factory defaults and reboot before enabling measurements.
+    ///
+    /// Appropriate for development and for applications that configure the device
+    /// from scratch on every boot. Note that this discards any saved settings
+    /// and writes to the device's flash memory on every start.
+    FactoryDefaults,
+    /// Keep the settings saved on the device and only enable measurements.
+    ///
+    /// Appropriate for provisioned units whose settings were saved beforehand
+    /// with `save_settings`.
+    SavedSettings,
+}
+
+/// Word-sized (16-bit) configuration values of the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConfigWord {
+    /// Measurement framerate in Hz
+    Framerate,
+    /// Signal strength threshold below which the dummy distance is returned
+    SignalStrengthThreshold,
+    /// Distance returned when the signal strength is below the threshold
+    DummyDistance,
+    /// Minimum valid distance
+    MinimumDistance,
+    /// Maximum valid distance
+    MaximumDistance,
+}
+
+/// How the distance is reported when the signal is lost.
+///
+/// Applied in software by `get_measurement_with_policy`. The signal is considered lost
+/// when the device reports an error or the signal strength is below 100 or saturated (65535).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LossPolicy {
+    /// Report the last distance measured with a reliable signal, or 0 if there is none yet.
+    HoldLast,
+    /// Report the given dummy distance, in centimeters.
+    Dummy(u16),
+    /// Report a distance of 0.
+    Zero,
+}
+
+/// Zero-copy view over the 10 bytes of a measurement read from the device.
+///
+/// Each getter decodes its field from the borrowed buffer on demand, so fields that are
+/// not used are never decoded. The view borrows the buffer the measurement was read into,
+/// so it cannot outlive it and the buffer cannot be reused for another read while the view exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeasurementView<'a> {
+    buffer: &'a [u8; 10],
+}
+
+impl<'a> MeasurementView<'a> {
+    /// Create a view over the content of registers 0x00 to 0x09.
+    pub fn new(buffer: &'a [u8; 10]) -> Self {
+        Self { buffer }
+    }
+
+    /// Little-endian word starting at `index`
+    fn word(&self, index: usize) -> u16 {
+        u16::from_le_bytes([self.buffer[index], self.buffer[index + 1]])
+    }
+
+    /// Distance in centimeters
+    pub fn distance(&self) -> u16 {
+        self.word(0)
+    }
+
+    /// Signal strength (amplitude in manual) value between 0 and 1000.
+    pub fn signal_strength(&self) -> u16 {
+        self.word(2)
+    }
+
+    /// Internal device temperature in °C with 0.01 precision.
+    pub fn temperature(&self) -> f32 {
+        // Temperature is a signed value in two's complement
+        self.word(4) as i16 as f32 / 100.0
+    }
+
+    /// Clock ticks since device was powered on, see [`crate::i2c::DEVICE_TICK_HZ`].
+    pub fn timestamp(&self) -> u16 {
+        self.word(6)
+    }
+
+    /// Error code
+    pub fn error(&self) -> u16 {
+        self.word(8)
+    }
+
+    /// Decode all fields into an owned [`SensorReading`].
+    pub fn to_reading(&self) -> SensorReading {
+        SensorReading {
+            distance: self.distance(),
+            signal_strength: self.signal_strength(),
+            temperature: self.temperature(),
+            timestamp: self.timestamp(),
+            error: self.error(),
+        }
+    }
+}
+
+/// Time base used to align the timestamps of several devices in software.
+///
+/// The device timestamp counts ticks since power-on and cannot be reset, there is
+/// no command for it. To compare timestamps of several devices, record the timestamp of
+/// each device at a common sync point and express later timestamps relative to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimestampBase {
+    /// Device timestamp at the sync point
+    sync_timestamp: u16,
+}
+
+impl TimestampBase {
+    /// Create a time base from the device timestamp read at the sync point.
+    pub fn new(sync_timestamp: u16) -> Self {
+        Self { sync_timestamp }
+    }
+
+    /// Device timestamp at the sync point.
+    pub fn sync_timestamp(&self) -> u16 {
+        self.sync_timestamp
+    }
+
+    /// Ticks elapsed between the sync point and `timestamp`.
+    ///
+    /// The result is computed with wrapping arithmetic and is only correct if
+    /// less than 65536 ticks elapsed since the sync point.
+    pub fn ticks_since_sync(&self, timestamp: u16) -> u16 {
+        timestamp.wrapping_sub(self.sync_timestamp)
+    }
+
+    /// Return a copy of the reading with its timestamp expressed relative to the sync point.
+    pub fn align(&self, reading: SensorReading) -> SensorReading {
+        SensorReading {
+            timestamp: self.ticks_since_sync(reading.timestamp),
+            ..reading
+        }
+    }
+}
+
+/// Snapshot of the device configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    /// Measurement framerate in Hz
+    pub framerate: u16,
+    /// Ranging mode
+    pub ranging_mode: RangingMode,
+    /// Power mode
+    pub power_mode: PowerMode,
+    /// Signal strength threshold below which the dummy distance is returned
+    pub signal_strength_threshold: u16,
+    /// Distance returned when the signal strength is below the threshold
+    pub dummy_distance: u16,
+    /// Minimum valid distance
+    pub minimum_distance: u16,
+    /// Maximum valid distance
+    pub maximum_distance: u16,
+}
+
+/// Statistics of the distances in a window of measurements.
+///
+/// Computed by the `Statistics` stream adapter from exact integer sums, so they don't drift
+/// as measurements enter and leave the window. Integer variants of the mean and standard
+/// deviation are available as unsigned Q24.8 fixed-point numbers of centimeters,
+/// see [`SensorReading::distance_q8()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DistanceStatistics {
+    /// Number of distances in the window
+    pub count: usize,
+    /// Minimum distance in centimeters
+    pub minimum: u16,
+    /// Maximum distance in centimeters
+    pub maximum: u16,
+    /// Sum of the distances
+    pub(crate) sum: u64,
+    /// Sum of the squared distances
+    pub(crate) sum_of_squares: u64,
+}
+
+impl DistanceStatistics {
+    /// Mean distance in centimeters.
+    pub fn mean(&self) -> f32 {
+        self.sum as f32 / self.count as f32
+    }
+
+    /// Mean distance as an unsigned Q24.8 fixed-point number of centimeters, rounded to the nearest 1/256 cm.
+    pub fn mean_q8(&self) -> u32 {
+        let count = self.count as u64;
+        (((self.sum << 8) + count / 2) / count) as u32
+    }
+
+    /// Population standard deviation of the distances in centimeters.
+    pub fn std_dev(&self) -> f32 {
+        self.std_dev_q8() as f32 / 256.0
+    }
+
+    /// Population standard deviation as an unsigned Q24.8 fixed-point number of centimeters,
+    /// rounded down to 1/256 cm.
+    pub fn std_dev_q8(&self) -> u32 {
+        let count = self.count as u128;
+        let sum = self.sum as u128;
+        // count² · variance, exact and never negative with integer sums
+        let scaled_variance = count * self.sum_of_squares as u128 - sum * sum;
+        ((scaled_variance << 16) / (count * count)).isqrt() as u32
+    }
+}
+
+/// Named configurations for common use cases.
+///
+/// | Preset | Framerate | Ranging mode | Power mode | Signal strength threshold | Dummy distance | Minimum distance | Maximum distance |
+/// |---|---|---|---|---|---|---|---|
+/// | `IndoorShortRange` | 100Hz | Continuous | Normal | 100 | 0 | 20cm | 300cm |
+/// | `OutdoorLongRange` | 50Hz | Continuous | Normal | 100 | 0 | 20cm | 800cm |
+/// | `LowPower` | 5Hz | Continuous | Power saving | 100 | 0 | 20cm | 800cm |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Preset {
+    /// Fast updates limited to the range over which the accuracy is ±6cm.
+    IndoorShortRange,
+    /// Full operating range, with a lower framerate so that each output averages
+    /// more internal measurements, which helps with weak returns in sunlight.
+    OutdoorLongRange,
+    /// Full operating range in power saving mode, which limits the framerate to 10Hz.
+    LowPower,
+}
+
+impl Preset {
+    /// Get the configuration applied by the preset.
+    pub fn config(&self) -> Config {
+        let (framerate, power_mode, maximum_distance) = match self {
+            Preset::IndoorShortRange => (100, PowerMode::Normal, 300),
+            Preset::OutdoorLongRange => (50, PowerMode::Normal, 800),
+            Preset::LowPower => (5, PowerMode::PowerSaving, 800),
+        };
+        Config {
+            framerate,
+            ranging_mode: RangingMode::Continuous,
+            power_mode,
+            signal_strength_threshold: 100,
+            dummy_distance: 0,
+            minimum_distance: 20,
+            maximum_distance,
+        }
+    }
+}
+
 /// Structure containing distance, signal strength, temperature, and timestamp.
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SensorReading {
     /// Distance in centimeters
     pub distance: u16,
@@ -61,8 +385,162 @@ pub struct SensorReading {
     pub signal_strength: u16,
     /// Internal device temperature in °C with 0.01 precision.
     pub temperature: f32,
-    /// Clock ticks since device was powered on.
+    /// Clock ticks since device was powered on, see [`crate::i2c::DEVICE_TICK_HZ`].
     pub timestamp: u16,
     /// Error code
     pub error: u16,
 }
+
+impl SensorReading {
+    /// Return a copy of the reading with a fixed offset applied to the distance.
+    ///
+    /// # Arguments
+    /// * `offset`: signed offset in centimeters added to the measured distance.
+    ///
+    /// # Notes
+    /// * The TF-Luna has no distance offset/calibration register, so mounting offsets
+    ///   (e.g. the sensor being recessed behind a window) have to be compensated in software.
+    /// * The resulting distance saturates at `0` and `u16::MAX` instead of wrapping around.
+    pub fn with_offset(self, offset: i16) -> Self {
+        Self {
+            distance: self.distance.saturating_add_signed(offset),
+            ..self
+        }
+    }
+
+    /// Distance as a fraction of `max`, clamped to `0.0..=1.0`.
+    ///
+    /// # Arguments
+    /// * `max`: distance in centimeters corresponding to `1.0`, e.g. the configured maximum distance.
+    ///
+    /// # Notes
+    /// * If `max` is `0`, `1.0` is returned.
+    pub fn distance_fraction(&self, max: u16) -> f32 {
+        if max == 0 {
+            return 1.0;
+        }
+        (self.distance as f32 / max as f32).min(1.0)
+    }
+
+    /// Distance as a fraction of `max` in permille, clamped to `0..=1000`.
+    ///
+    /// Integer alternative to [`SensorReading::distance_fraction()`].
+    ///
+    /// # Arguments
+    /// * `max`: distance in centimeters corresponding to `1000`, e.g. the configured maximum distance.
+    ///
+    /// # Notes
+    /// * If `max` is `0`, `1000` is returned.
+    pub fn distance_permille(&self, max: u16) -> u16 {
+        if max == 0 {
+            return 1000;
+        }
+        (self.distance as u32 * 1000 / max as u32).min(1000) as u16
+    }
+
+    /// Distance in millimeters.
+    ///
+    /// The device only reports whole centimeters, so this is always a multiple of 10.
+    /// A `u32` is returned since distances above 6553cm don't fit in a `u16` once in millimeters.
+    pub fn distance_mm(&self) -> u32 {
+        self.distance as u32 * 10
+    }
+
+    /// Distance in meters.
+    pub fn distance_m(&self) -> f32 {
+        self.distance as f32 / 100.0
+    }
+
+    /// Distance as an unsigned Q24.8 fixed-point number of centimeters.
+    ///
+    /// The upper 24 bits hold the integer part and the lower 8 bits the fractional part
+    /// in 1/256 cm, i.e. the distance multiplied by 256. The device only reports whole
+    /// centimeters, the fractional part is only used by the results of integer filters
+    /// such as [`SensorReading::average_distance_q8()`].
+    pub fn distance_q8(&self) -> u32 {
+        (self.distance as u32) << 8
+    }
+
+    /// Average distance of several readings as an unsigned Q24.8 fixed-point number of centimeters.
+    ///
+    /// Unlike averaging the integer distances, the fractional part of the average is preserved,
+    /// see [`SensorReading::distance_q8()`] for the format.
+    ///
+    /// # Returns
+    /// * `Some(u32)`: average distance rounded to the nearest 1/256 cm.
+    /// * `None`: if `readings` is empty.
+    pub fn average_distance_q8(readings: &[SensorReading]) -> Option<u32> {
+        if readings.is_empty() {
+            return None;
+        }
+        let count = readings.len() as u64;
+        let sum: u64 = readings
+            .iter()
+            .map(|reading| reading.distance_q8() as u64)
+            .sum();
+        Some(((sum + count / 2) / count) as u32)
+    }
+
+    /// Internal device temperature in °F.
+    pub fn temperature_fahrenheit(&self) -> f32 {
+        self.temperature * 9.0 / 5.0 + 32.0
+    }
+
+    /// Internal device temperature in K.
+    pub fn temperature_kelvin(&self) -> f32 {
+        self.temperature + 273.15
+    }
+
+    /// Whether the internal device temperature is above the maximum operating temperature (60°C).
+    ///
+    /// # Notes
+    /// * The TF-Luna neither throttles nor shuts down on over-temperature, and there is no
+    ///   register to configure such a behavior. The error register has no documented
+    ///   over-temperature flag either, so this is based on the reported temperature.
+    /// * The reported temperature is the internal chip temperature, which is usually a few
+    ///   degrees above the ambient temperature the datasheet's operating range refers to,
+    ///   so this is a conservative indicator.
+    pub fn is_over_temperature(&self) -> bool {
+        self.temperature > MAXIMUM_OPERATING_TEMPERATURE
+    }
+
+    /// Whether the signal is saturated, i.e. the signal strength is 65535.
+    ///
+    /// As per the datasheet, this happens when the target is too close or too reflective
+    /// and the distance is not reliable.
+    pub fn is_saturated(&self) -> bool {
+        self.signal_strength == u16::MAX
+    }
+
+    /// Whether the signal strength is below the given threshold.
+    ///
+    /// The device reports the dummy distance instead of the measured one below its
+    /// signal strength threshold, see `set_signal_strength_threshold`.
+    pub fn is_below_threshold(&self, threshold: u16) -> bool {
+        self.signal_strength < threshold
+    }
+
+    /// Whether the distance can be trusted.
+    ///
+    /// That is the case when the device reports no error and the signal strength is at
+    /// least 100, the reliability limit given in the datasheet, without being saturated.
+    pub fn is_valid(&self) -> bool {
+        self.error == 0
+            && !self.is_below_threshold(MINIMUM_RELIABLE_SIGNAL_STRENGTH)
+            && !self.is_saturated()
+    }
+
+    /// Distance as a [`uom`] length quantity.
+    #[cfg(feature = "uom")]
+    pub fn length(&self) -> uom::si::f32::Length {
+        uom::si::f32::Length::new::<uom::si::length::centimeter>(self.distance as f32)
+    }
+
+    /// Internal device temperature as a [`uom`] thermodynamic temperature quantity.
+    #[cfg(feature = "uom")]
+    pub fn temperature_uom(&self) -> uom::si::f32::ThermodynamicTemperature {
+        uom::si::f32::ThermodynamicTemperature::new::<
+            uom::si::thermodynamic_temperature::degree_celsius,
+        >(self.temperature)
+    }
+}
@@ -0,0 +1,49 @@
+//! Interface for the UART protocol.
+//!
+//! When pin 5 is left unconnected or connected to 3.3V, TF-Luna enters UART mode.
+//! In this mode, pin 2 is used as RXD (receive) and pin 3 as TXD (transmit).
+//!
+//! | Baud rate | 115200 (default) |
+//! |---|---|
+//! | Data bits | 8 |
+//! | Stop bits | 1 |
+//! | Parity | None |
+//!
+//! # Frames
+//!
+//! Measurements are sent by the device in 9-byte data frames:
+//! `0x59 0x59 Dist_L Dist_H Amp_L Amp_H Temp_L Temp_H Checksum`.
+//!
+//! Commands and their responses are sent in frames of variable length:
+//! `0x5A Len ID Payload... Checksum`, where `Len` is the length of the whole frame.
+//!
+//! In both cases, the checksum is the low byte of the sum of all preceding bytes of the frame.
+//!
+//! The serial port is any type implementing the [`embedded-io`](https://crates.io/crates/embedded-io)
+//! `Read` and `Write` traits, or their [`embedded-io-async`](https://crates.io/crates/embedded-io-async)
+//! counterparts for the asynchronous interface.
+
+mod constants;
+mod types;
+
+#[path = "uart"]
+#[cfg(feature = "async")]
+pub mod asynchronous {
+    //! Asynchronous UART interface
+    use bisync::asynchronous::*;
+    #[allow(clippy::duplicate_mod)]
+    mod device;
+    pub use device::*;
+}
+
+#[path = "uart"]
+pub mod blocking {
+    //! Blocking UART interface
+    use bisync::synchronous::*;
+    #[allow(clippy::duplicate_mod)]
+    mod device;
+    pub use device::*;
+}
+
+pub use blocking::TFLuna;
+pub use types::{Error, compute_checksum, verify_frame};
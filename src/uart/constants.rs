@@ -0,0 +1,36 @@
+/// First and second bytes of a data frame
+pub const DATA_FRAME_HEADER: u8 = 0x59;
+/// Length of a data frame in bytes, including the header and the checksum
+pub const DATA_FRAME_LENGTH: usize = 9;
+/// First byte of a command or response frame
+pub const COMMAND_FRAME_HEADER: u8 = 0x5A;
+/// Length of a command or response frame without payload, i.e. header, length, ID and checksum
+pub const COMMAND_FRAME_OVERHEAD: usize = 4;
+/// Largest payload sent in a command frame
+pub const COMMAND_MAX_PAYLOAD_LENGTH: usize = 4;
+/// Number of bytes after which to give up looking for a frame header
+///
+/// While measurements are output, data frames can arrive before the response to a command.
+pub const SYNC_MAX_BYTES: usize = 64;
+
+/// Command to get the firmware version
+pub const GET_VERSION_COMMAND_ID: u8 = 0x01;
+/// Command to reboot the device
+pub const SYSTEM_RESET_COMMAND_ID: u8 = 0x02;
+/// Command to set the framerate
+pub const FRAMERATE_COMMAND_ID: u8 = 0x03;
+/// Command to trigger a single measurement
+pub const TRIGGER_COMMAND_ID: u8 = 0x04;
+/// Command to enable or disable the output of measurements
+pub const OUTPUT_ENABLE_COMMAND_ID: u8 = 0x07;
+/// Command to restore the factory defaults
+pub const RESTORE_FACTORY_DEFAULTS_COMMAND_ID: u8 = 0x10;
+/// Command to save the current settings
+pub const SAVE_SETTINGS_COMMAND_ID: u8 = 0x11;
+
+/// Payload of the output enable command to enable the output
+pub const ENABLE_COMMAND_VALUE: u8 = 1;
+/// Payload of the output enable command to disable the output
+pub const DISABLE_COMMAND_VALUE: u8 = 0;
+/// Status returned by the device when a command succeeded
+pub const SUCCESS_STATUS: u8 = 0;
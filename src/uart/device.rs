@@ -0,0 +1,380 @@
+use crate::constants::{FRAMERATE_OPTIONS, REBOOT_DELAY_MS};
+use crate::types::{FirmwareVersion, SensorReading};
+use crate::uart::constants;
+use crate::uart::types::{Error, compute_checksum, verify_frame};
+
+use super::{bisync, only_async, only_sync};
+
+#[only_sync]
+use embedded_hal::delay::DelayNs;
+#[only_sync]
+use embedded_io::{Read, Write};
+
+#[only_async]
+use embedded_hal_async::delay::DelayNs;
+#[only_async]
+use embedded_io_async::{Read, Write};
+
+/// TF-Luna UART controller/driver
+///
+/// # Simple example
+///
+/// ```rust
+/// let mut tfluna = TFLuna::new(serial, Delay::new())?;
+/// // Enable the output of measurements
+/// tfluna.enable()?;
+/// // Get measurement from device
+/// let measurement = tfluna.get_measurement()?;
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TFLuna<S, D> {
+    /// Concrete serial port implementation.
+    serial: S,
+    /// Concrete delay implementation
+    delay: D,
+    /// Whether the device outputs data frames, as last set through the driver
+    continuous_output: bool,
+}
+
+impl<S, D> TFLuna<S, D>
+where
+    S: Read + Write,
+    D: DelayNs,
+{
+    /// Associated method to create a new instance of the controller
+    ///
+    /// # Notes
+    /// * No data is sent to the device, in particular its saved settings are left untouched.
+    pub fn new(serial: S, delay: D) -> Result<Self, Error<S::Error>> {
+        Ok(Self {
+            serial,
+            delay,
+            continuous_output: true,
+        })
+    }
+
+    /// Read a single byte while looking for a frame, counting it in `consumed`.
+    #[bisync]
+    async fn read_sync_byte(&mut self, consumed: &mut usize) -> Result<u8, Error<S::Error>> {
+        if *consumed >= constants::SYNC_MAX_BYTES {
+            return Err(Error::Timeout);
+        }
+        *consumed += 1;
+        let mut byte = [0; 1];
+        self.serial.read_exact(&mut byte).await?;
+        Ok(byte[0])
+    }
+
+    /// Send a command frame.
+    #[bisync]
+    async fn send_command(&mut self, id: u8, payload: &[u8]) -> Result<(), Error<S::Error>> {
+        if payload.len() > constants::COMMAND_MAX_PAYLOAD_LENGTH {
+            return Err(Error::InvalidParameter);
+        }
+        let length = payload.len() + constants::COMMAND_FRAME_OVERHEAD;
+        let mut frame =
+            [0; constants::COMMAND_MAX_PAYLOAD_LENGTH + constants::COMMAND_FRAME_OVERHEAD];
+        frame[0] = constants::COMMAND_FRAME_HEADER;
+        frame[1] = length as u8;
+        frame[2] = id;
+        frame[3..length - 1].copy_from_slice(payload);
+        frame[length - 1] = compute_checksum(&frame[..length - 1]);
+        self.serial.write_all(&frame[..length]).await?;
+        self.serial.flush().await?;
+        Ok(())
+    }
+
+    /// Read the response frame to a command and return its payload.
+    ///
+    /// Data frames received before the response are discarded as a whole, so that a `0x5A`
+    /// byte in their payload is not mistaken for the header of the response. Command frames
+    /// with another length, ID or a wrong checksum are skipped as well.
+    #[bisync]
+    async fn read_response<const N: usize>(&mut self, id: u8) -> Result<[u8; N], Error<S::Error>> {
+        let length = N + constants::COMMAND_FRAME_OVERHEAD;
+        let mut frame =
+            [0; constants::COMMAND_MAX_PAYLOAD_LENGTH + constants::COMMAND_FRAME_OVERHEAD];
+        if length > frame.len() {
+            return Err(Error::InvalidParameter);
+        }
+        let mut consumed = 0;
+        // Byte read ahead that still has to be looked at
+        let mut next = None;
+        loop {
+            let byte = match next.take() {
+                Some(byte) => byte,
+                None => self.read_sync_byte(&mut consumed).await?,
+            };
+            match byte {
+                constants::DATA_FRAME_HEADER => {
+                    let second = self.read_sync_byte(&mut consumed).await?;
+                    if second == constants::DATA_FRAME_HEADER {
+                        let mut data = [0; constants::DATA_FRAME_LENGTH - 2];
+                        self.serial.read_exact(&mut data).await?;
+                        consumed += data.len();
+                    } else {
+                        next = Some(second);
+                    }
+                }
+                constants::COMMAND_FRAME_HEADER => {
+                    let received_length = self.read_sync_byte(&mut consumed).await?;
+                    if received_length as usize != length {
+                        next = Some(received_length);
+                        continue;
+                    }
+                    frame[0] = constants::COMMAND_FRAME_HEADER;
+                    frame[1] = received_length;
+                    self.serial.read_exact(&mut frame[2..length]).await?;
+                    consumed += length - 2;
+                    if verify_frame::<S::Error>(&frame[..length]).is_ok() && frame[2] == id {
+                        let mut payload = [0; N];
+                        payload.copy_from_slice(&frame[3..length - 1]);
+                        return Ok(payload);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Send a command whose response is a single status byte.
+    #[bisync]
+    async fn send_status_command(&mut self, id: u8) -> Result<(), Error<S::Error>> {
+        self.send_command(id, &[]).await?;
+        let [status] = self.read_response::<1>(id).await?;
+        if status != constants::SUCCESS_STATUS {
+            return Err(Error::InvalidData(status));
+        }
+        Ok(())
+    }
+
+    /// Save current settings to persistent storage.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the settings were saved.
+    /// * `Err(Error::InvalidData)`: if the device reported a failure.
+    /// * `Err(Error::Serial(SerialError))`: if there was a serial port error.
+    #[bisync]
+    pub async fn save_settings(&mut self) -> Result<(), Error<S::Error>> {
+        self.send_status_command(constants::SAVE_SETTINGS_COMMAND_ID)
+            .await
+    }
+
+    /// Reboot the device.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the device acknowledged the reboot.
+    /// * `Err(Error::InvalidData)`: if the device reported a failure.
+    /// * `Err(Error::Serial(SerialError))`: if there was a serial port error.
+    ///
+    /// # Notes
+    /// * Unsaved settings are lost.
+    /// * The device is given 500ms to reboot before returning, using the delay passed to
+    ///   [`TFLuna::new()`].
+    /// * The device outputs data frames again after the reboot.
+    #[bisync]
+    pub async fn reboot(&mut self) -> Result<(), Error<S::Error>> {
+        self.send_status_command(constants::SYSTEM_RESET_COMMAND_ID)
+            .await?;
+        self.continuous_output = true;
+        self.delay.delay_ms(REBOOT_DELAY_MS).await;
+        Ok(())
+    }
+
+    /// Restore all settings to factory defaults.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the factory defaults were restored.
+    /// * `Err(Error::InvalidData)`: if the device reported a failure.
+    /// * `Err(Error::Serial(SerialError))`: if there was a serial port error.
+    ///
+    /// # Notes
+    /// * The output of data frames is enabled by the factory defaults.
+    #[bisync]
+    pub async fn restore_factory_defaults(&mut self) -> Result<(), Error<S::Error>> {
+        self.send_status_command(constants::RESTORE_FACTORY_DEFAULTS_COMMAND_ID)
+            .await?;
+        self.continuous_output = true;
+        Ok(())
+    }
+
+    /// Enable the output of measurements.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the output was enabled.
+    /// * `Err(Error::InvalidData)`: if the device answered with an invalid response.
+    /// * `Err(Error::Serial(SerialError))`: if there was a serial port error.
+    #[bisync]
+    pub async fn enable(&mut self) -> Result<(), Error<S::Error>> {
+        self.set_continuous_output(true).await
+    }
+
+    /// Disable the output of measurements.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the output was disabled.
+    /// * `Err(Error::InvalidData)`: if the device answered with an invalid response.
+    /// * `Err(Error::Serial(SerialError))`: if there was a serial port error.
+    #[bisync]
+    pub async fn disable(&mut self) -> Result<(), Error<S::Error>> {
+        self.set_continuous_output(false).await
+    }
+
+    /// Enable or disable the continuous output of data frames.
+    ///
+    /// # Arguments
+    /// * `enabled`: whether the device should output a data frame for every measurement.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the output was enabled or disabled.
+    /// * `Err(Error::InvalidData)`: if the device answered with an invalid response.
+    /// * `Err(Error::Serial(SerialError))`: if there was a serial port error.
+    ///
+    /// # Notes
+    /// * Unlike on I2C, where measurements are always available in the registers, a disabled
+    ///   output means that nothing is received. [`TFLuna::get_measurement()`] then returns
+    ///   `Err(Error::InvalidState)` instead of waiting for a frame that never arrives.
+    /// * The device is assumed to output data frames until this method is called,
+    ///   as it does with its factory defaults. Rebooting or restoring the factory defaults
+    ///   enables the output again.
+    /// * [`TFLuna::enable()`] and [`TFLuna::disable()`] are shorthands for this method.
+    #[bisync]
+    pub async fn set_continuous_output(&mut self, enabled: bool) -> Result<(), Error<S::Error>> {
+        let value = if enabled {
+            constants::ENABLE_COMMAND_VALUE
+        } else {
+            constants::DISABLE_COMMAND_VALUE
+        };
+        self.send_command(constants::OUTPUT_ENABLE_COMMAND_ID, &[value])
+            .await?;
+        let [echo] = self
+            .read_response::<1>(constants::OUTPUT_ENABLE_COMMAND_ID)
+            .await?;
+        if echo != value {
+            return Err(Error::InvalidData(echo));
+        }
+        self.continuous_output = enabled;
+        Ok(())
+    }
+
+    /// Set the measurement framerate in Hz.
+    ///
+    /// # Arguments
+    /// * `value`: desired framerate in Hz.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if framerate was set successfully.
+    /// * `Err(Error::InvalidParameter)`: if framerate is invalid.
+    /// * `Err(Error::InvalidData)`: if the device answered with an invalid response.
+    /// * `Err(Error::Serial(SerialError))`: if there was a serial port error.
+    ///
+    /// # Notes
+    /// * The same framerates as in the I2C interface are accepted, i.e. the ones listed in
+    ///   the manual, and 0, which stops the continuous output.
+    #[bisync]
+    pub async fn set_framerate(&mut self, value: u16) -> Result<(), Error<S::Error>> {
+        if value != 0 && !FRAMERATE_OPTIONS.contains(&value) {
+            return Err(Error::InvalidParameter);
+        }
+        let payload = value.to_le_bytes();
+        self.send_command(constants::FRAMERATE_COMMAND_ID, &payload)
+            .await?;
+        let echo = self
+            .read_response::<2>(constants::FRAMERATE_COMMAND_ID)
+            .await?;
+        if echo != payload {
+            return Err(Error::InvalidData(echo[0]));
+        }
+        Ok(())
+    }
+
+    /// Get the firmware version of the device.
+    ///
+    /// # Returns
+    /// * `Ok(FirmwareVersion)`: firmware version.
+    /// * `Err(Error::InvalidData)`: if the device answered with an invalid response.
+    /// * `Err(Error::Serial(SerialError))`: if there was a serial port error.
+    #[bisync]
+    pub async fn get_firmware_version(&mut self) -> Result<FirmwareVersion, Error<S::Error>> {
+        self.send_command(constants::GET_VERSION_COMMAND_ID, &[])
+            .await?;
+        let [revision, minor, major] = self
+            .read_response::<3>(constants::GET_VERSION_COMMAND_ID)
+            .await?;
+        Ok(FirmwareVersion {
+            major,
+            minor,
+            revision,
+        })
+    }
+
+    /// Trigger a single measurement.
+    ///
+    /// # Returns
+    /// * `Ok(())`: if the trigger command was sent.
+    /// * `Err(Error::Serial(SerialError))`: if there was a serial port error.
+    ///
+    /// # Notes
+    /// * The device answers with a data frame, read it with [`TFLuna::get_measurement()`].
+    /// * Set the framerate to 0 first to stop the continuous output.
+    #[bisync]
+    pub async fn trigger_measurement(&mut self) -> Result<(), Error<S::Error>> {
+        self.send_command(constants::TRIGGER_COMMAND_ID, &[]).await
+    }
+
+    /// Read the next measurement output by the device.
+    ///
+    /// # Returns
+    /// * `Ok(SensorReading)`: Structure containing distance, signal strength and temperature.
+    /// * `Err(Error::InvalidData)`: if the checksum of the data frame doesn't match.
+    /// * `Err(Error::Timeout)`: if no data frame header was received.
+    /// * `Err(Error::InvalidState)`: if the output was disabled through the driver,
+    ///   see [`TFLuna::set_continuous_output()`]. Nothing is read from the serial port.
+    /// * `Err(Error::Serial(SerialError))`: if there was a serial port error.
+    ///
+    /// # Notes
+    /// * Bytes received before the `0x59 0x59` header are discarded.
+    /// * The data frame has no timestamp and no error code, they are always 0.
+    /// * The temperature is sent in 1/8 °C with an offset of 256°C and is converted to °C.
+    #[bisync]
+    pub async fn get_measurement(&mut self) -> Result<SensorReading, Error<S::Error>> {
+        if !self.continuous_output {
+            return Err(Error::InvalidState);
+        }
+        let mut frame = [0; constants::DATA_FRAME_LENGTH];
+        frame[0] = constants::DATA_FRAME_HEADER;
+        frame[1] = constants::DATA_FRAME_HEADER;
+        let mut byte = [0; 1];
+        let mut headers = 0;
+        for _ in 0..constants::SYNC_MAX_BYTES {
+            self.serial.read_exact(&mut byte).await?;
+            headers = if byte[0] == constants::DATA_FRAME_HEADER {
+                headers + 1
+            } else {
+                0
+            };
+            if headers == 2 {
+                break;
+            }
+        }
+        if headers < 2 {
+            return Err(Error::Timeout);
+        }
+        self.serial.read_exact(&mut frame[2..]).await?;
+        verify_frame(&frame)?;
+        let temperature = u16::from_le_bytes([frame[6], frame[7]]);
+        Ok(SensorReading {
+            distance: u16::from_le_bytes([frame[2], frame[3]]),
+            signal_strength: u16::from_le_bytes([frame[4], frame[5]]),
+            temperature: temperature as f32 / 8.0 - 256.0,
+            timestamp: 0,
+            error: 0,
+        })
+    }
+
+    /// Consume the controller and return the serial port and delay.
+    pub fn release(self) -> (S, D) {
+        (self.serial, self.delay)
+    }
+}
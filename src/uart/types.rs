@@ -0,0 +1,66 @@
+use embedded_io::{Error as SerialErrorTrait, ReadExactError};
+
+/// UART Error enum
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<SerialError: SerialErrorTrait> {
+    /// Wrapped serial port error
+    Serial(SerialError),
+    /// Invalid data returned by device, e.g. a frame with a wrong checksum
+    InvalidData(u8),
+    /// Invalid parameter passed to method
+    InvalidParameter,
+    /// No frame was received within the expected number of bytes
+    Timeout,
+    /// Operation not possible in the current device mode, e.g. reading a measurement
+    /// while the continuous output is disabled
+    InvalidState,
+    /// Other error
+    Other,
+}
+
+impl<SerialError> From<SerialError> for Error<SerialError>
+where
+    SerialError: SerialErrorTrait,
+{
+    fn from(value: SerialError) -> Self {
+        Error::Serial(value)
+    }
+}
+
+impl<SerialError> From<ReadExactError<SerialError>> for Error<SerialError>
+where
+    SerialError: SerialErrorTrait,
+{
+    fn from(value: ReadExactError<SerialError>) -> Self {
+        match value {
+            ReadExactError::UnexpectedEof => Error::Timeout,
+            ReadExactError::Other(e) => Error::Serial(e),
+        }
+    }
+}
+
+/// Compute the checksum of a frame, i.e. the low byte of the sum of its bytes.
+///
+/// # Arguments
+/// * `frame`: bytes of the frame preceding the checksum, starting with the header.
+pub fn compute_checksum(frame: &[u8]) -> u8 {
+    frame.iter().fold(0, |sum, &byte| sum.wrapping_add(byte))
+}
+
+/// Verify the checksum of a complete frame.
+///
+/// # Arguments
+/// * `frame`: data or command frame, ending with its checksum.
+///
+/// # Returns
+/// * `Ok(())`: if the last byte is the checksum of the preceding bytes.
+/// * `Err(Error::InvalidData(u8))`: with the received checksum if it doesn't match.
+/// * `Err(Error::InvalidParameter)`: if the frame is empty.
+pub fn verify_frame<SerialError: SerialErrorTrait>(frame: &[u8]) -> Result<(), Error<SerialError>> {
+    let (&received, bytes) = frame.split_last().ok_or(Error::InvalidParameter)?;
+    if compute_checksum(bytes) != received {
+        return Err(Error::InvalidData(received));
+    }
+    Ok(())
+}
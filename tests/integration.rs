@@ -356,6 +356,7 @@ mod tests {
 
     // This test is ignored for now because I couldn't make it work
     // The ultra-low power mode seems to be very finicky.
+    // The firmware of the test device is listed as unreliable in `ultra_low_power_reliable`.
     #[test]
     #[ignore]
     fn test_ultra_low_power_mode_read_measurement(context: Context) {
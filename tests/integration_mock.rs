@@ -1,24 +1,64 @@
-#[cfg(test)]
+// Logs are discarded, the logger is only needed to link the tests with defmt logging enabled
+#[cfg(all(test, feature = "log-transitions"))]
+mod logger {
+    #[defmt::global_logger]
+    struct Logger;
+
+    unsafe impl defmt::Logger for Logger {
+        fn acquire() {}
+        unsafe fn flush() {}
+        unsafe fn release() {}
+        unsafe fn write(_bytes: &[u8]) {}
+    }
+
+    defmt::timestamp!("{=u8}", 0);
+}
+
+#[cfg(all(test, any(feature = "async", feature = "i2c-blocking")))]
 mod test {
     extern crate std;
     use std::vec::Vec;
 
+    use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+    #[cfg(feature = "i2c-blocking")]
+    use embedded_hal_mock::eh1::MockError as PinMockError;
     use embedded_hal_mock::eh1::delay::StdSleep as Delay;
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
     use embedded_hal_mock::eh1::i2c::{Mock as I2cTraitMock, Transaction as I2cTraitTransaction};
 
+    #[cfg(feature = "i2c-blocking")]
+    use embedded_tfluna::i2c::AddressSet;
     #[cfg(feature = "async")]
-    use embedded_tfluna::i2c::asynchronous::TFLuna as TFLunaAsync;
-    use embedded_tfluna::i2c::blocking::TFLuna as TFLunaBlocking;
-    use embedded_tfluna::i2c::{Address, DEFAULT_SLAVE_ADDRESS, Error};
-    use embedded_tfluna::{FirmwareVersion, SensorReading, SerialNumber};
+    use embedded_tfluna::i2c::asynchronous::{
+        ProximitySwitch as ProximitySwitchAsync, TFLuna as TFLunaAsync,
+        TFLunaBuilder as TFLunaBuilderAsync,
+    };
+    #[cfg(feature = "i2c-blocking")]
+    use embedded_tfluna::i2c::blocking::{
+        ProximitySwitch as ProximitySwitchBlocking, TFLuna as TFLunaBlocking,
+    };
+    use embedded_tfluna::i2c::{
+        Address, DEFAULT_SLAVE_ADDRESS, Error, PowerModeTiming, ProvisioningCheck, ReadMode,
+        RecoveryHint, RetryConfig,
+    };
+    use embedded_tfluna::{
+        Config, ConfigWord, FirmwareVersion, LossPolicy, MeasurementView, PowerMode, Preset,
+        RangingMode, SensorReading, SerialNumber, Signature, Startup, TimestampBase,
+    };
 
     use rstest::*;
 
     enum Transaction<'a> {
         Write(u8, &'a [u8]),
         Read(u8, &'a [u8]),
+        WriteError(u8, &'a [u8], ErrorKind),
+        ReadError(u8, &'a [u8], ErrorKind),
     }
 
+    const NACK: ErrorKind = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+
     impl<'a> From<Transaction<'a>> for I2cTraitTransaction {
         fn from(transaction: Transaction) -> Self {
             match transaction {
@@ -33,33 +73,43 @@ mod test {
                     write_data.append(&mut values);
                     I2cTraitTransaction::write(DEFAULT_SLAVE_ADDRESS, write_data)
                 }
+                Transaction::WriteError(register_address, value, error) => {
+                    I2cTraitTransaction::from(Transaction::Write(register_address, value))
+                        .with_error(error)
+                }
+                Transaction::ReadError(register_address, value, error) => {
+                    I2cTraitTransaction::from(Transaction::Read(register_address, value))
+                        .with_error(error)
+                }
             }
         }
     }
 
     /// Returns vector of i2c transaction expectations for an I2C read operation
+    #[cfg(feature = "i2c-blocking")]
     fn read_expectations(register_address: u8, value: &[u8]) -> Vec<I2cTraitTransaction> {
-        let expectations = Vec::from([I2cTraitTransaction::write_read(
+        Vec::from([I2cTraitTransaction::write_read(
             DEFAULT_SLAVE_ADDRESS,
             Vec::from([register_address]),
             Vec::from(value),
-        )]);
-        expectations
+        )])
     }
 
     /// Returns vector of i2c transaction expectations for an I2C write operation
+    #[cfg(feature = "i2c-blocking")]
     fn write_expectations(register_address: u8, value: u8) -> Vec<I2cTraitTransaction> {
-        let expectations = Vec::from([I2cTraitTransaction::write(
+        Vec::from([I2cTraitTransaction::write(
             DEFAULT_SLAVE_ADDRESS,
             Vec::from([register_address, value]),
-        )]);
-        expectations
+        )])
     }
 
+    #[cfg(feature = "i2c-blocking")]
     fn setup(i2c: &mut I2cTraitMock) -> TFLunaBlocking<&mut I2cTraitMock, Delay> {
         TFLunaBlocking::new(i2c, Address::default(), Delay {}).unwrap()
     }
 
+    #[cfg(feature = "i2c-blocking")]
     fn i2c_blocking(transactions: Vec<Transaction>) -> I2cTraitMock {
         let expectations = transactions
             .into_iter()
@@ -68,6 +118,7 @@ mod test {
         I2cTraitMock::new(&expectations)
     }
 
+    #[cfg(feature = "i2c-blocking")]
     fn device_blocking(i2c: &mut I2cTraitMock) -> TFLunaBlocking<&mut I2cTraitMock, Delay> {
         TFLunaBlocking::new(i2c, Address::default(), Delay {}).unwrap()
     }
@@ -86,6 +137,7 @@ mod test {
         TFLunaAsync::new(i2c, Address::default(), Delay {}).unwrap()
     }
 
+    #[cfg(feature = "i2c-blocking")]
     #[rstest]
     #[case::enable_then_disable(&mut i2c_blocking(Vec::from([
         Transaction::Write(0x25, &[1]),
@@ -101,7 +153,7 @@ mod test {
     #[cfg(feature = "async")]
     #[rstest]
     #[tokio::test]
-    #[case::enable_then_disable(&mut i2c_blocking(Vec::from([
+    #[case::enable_then_disable(&mut i2c_async(Vec::from([
         Transaction::Write(0x25, &[1]),
         Transaction::Write(0x25, &[0]),
     ])))]
@@ -112,6 +164,7 @@ mod test {
         i2c.done();
     }
 
+    #[cfg(feature = "i2c-blocking")]
     #[rstest]
     #[case::reboot(&mut i2c_blocking(Vec::from([
         Transaction::Write(0x21, &[2]),
@@ -134,6 +187,7 @@ mod test {
         i2c.done();
     }
 
+    #[cfg(feature = "i2c-blocking")]
     #[rstest]
     #[case::firmware_2_1_0(&mut i2c_blocking(Vec::from([
         Transaction::Read(0x0A, &[0, 1, 2]),
@@ -185,6 +239,7 @@ mod test {
         i2c.done();
     }
 
+    #[cfg(feature = "i2c-blocking")]
     #[rstest]
     #[case::all_zeros(&mut i2c_blocking(Vec::from([
         Transaction::Read(0x10, &[0; 14]),
@@ -236,6 +291,7 @@ mod test {
         i2c.done();
     }
 
+    #[cfg(feature = "i2c-blocking")]
     #[rstest]
     #[case::some_measurement(&mut i2c_blocking(Vec::from([
         Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
@@ -246,6 +302,15 @@ mod test {
             timestamp: 0,
             error: 0,
     })]
+    #[case::below_zero_temperature(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0x0C, 0xFE, 0, 0, 0, 0]),
+    ])), SensorReading {
+            distance: 10,
+            signal_strength: 100,
+            temperature: -5.0,
+            timestamp: 0,
+            error: 0,
+    })]
     fn test_measure_blocking(
         #[case] i2c: &mut I2cTraitMock,
         #[case] expected_measurement: SensorReading,
@@ -265,8 +330,7 @@ mod test {
 
     #[cfg(feature = "async")]
     #[rstest]
-    #[tokio::test]
-    #[case::some_measurement(&mut i2c_blocking(Vec::from([
+    #[case::some_measurement(&mut i2c_async(Vec::from([
         Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
     ])), SensorReading {
             distance: 10,
@@ -275,6 +339,16 @@ mod test {
             timestamp: 0,
             error: 0,
     })]
+    #[case::below_zero_temperature(&mut i2c_async(Vec::from([
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0x0C, 0xFE, 0, 0, 0, 0]),
+    ])), SensorReading {
+            distance: 10,
+            signal_strength: 100,
+            temperature: -5.0,
+            timestamp: 0,
+            error: 0,
+    })]
+    #[tokio::test]
     async fn test_measure_async(
         #[case] i2c: &mut I2cTraitMock,
         #[case] expected_measurement: SensorReading,
@@ -292,12 +366,18 @@ mod test {
         i2c.done();
     }
 
+    #[cfg(feature = "i2c-blocking")]
     #[rstest]
+    #[case::framerate_3(&mut i2c_blocking(Vec::new()), 3)]
+    #[case::framerate_4(&mut i2c_blocking(Vec::new()), 4)]
     #[case::framerate_240(&mut i2c_blocking(Vec::new()), 240)]
     #[case::framerate_500(&mut i2c_blocking(Vec::new()), 500)]
     fn test_invalid_framerate_blocking(#[case] i2c: &mut I2cTraitMock, #[case] framerate: u16) {
         let mut device = device_blocking(i2c);
-        assert!(device.set_framerate(framerate).is_err());
+        assert!(matches!(
+            device.set_framerate(framerate),
+            Err(Error::InvalidParameter)
+        ));
         i2c.done();
     }
 
@@ -313,6 +393,7 @@ mod test {
         i2c.done();
     }
 
+    #[cfg(feature = "i2c-blocking")]
     #[rstest]
     #[case::address_1(&mut i2c_blocking(Vec::new()), 1)]
     #[case::address_200(&mut i2c_blocking(Vec::new()), 200)]
@@ -333,4 +414,3914 @@ mod test {
         assert!(device.set_slave_address(address).await.is_err());
         i2c.done();
     }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::pin_low(PinState::Low, false)]
+    #[case::pin_high(PinState::High, true)]
+    fn test_is_data_ready_blocking(#[case] pin_state: PinState, #[case] expected: bool) {
+        let mut i2c = I2cTraitMock::new(&write_expectations(0x25, 1));
+        let mut pin = PinMock::new(&[PinTransaction::get(pin_state)]);
+        let mut device = setup(&mut i2c).with_data_ready(&mut pin);
+        device.enable().unwrap();
+        assert_eq!(device.is_data_ready().unwrap(), expected);
+        pin.done();
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[case::pin_low(PinState::Low, false)]
+    #[case::pin_high(PinState::High, true)]
+    fn test_is_data_ready_async(#[case] pin_state: PinState, #[case] expected: bool) {
+        let mut i2c = i2c_async(Vec::new());
+        let mut pin = PinMock::new(&[PinTransaction::get(pin_state)]);
+        let mut device = device_async(&mut i2c).with_data_ready(&mut pin);
+        assert_eq!(device.is_data_ready().unwrap(), expected);
+        pin.done();
+        i2c.done();
+    }
+
+    #[rstest]
+    #[case::positive(100, 5, 105)]
+    #[case::negative(100, -5, 95)]
+    #[case::saturate_at_zero(3, -5, 0)]
+    #[case::saturate_at_max(u16::MAX - 1, 5, u16::MAX)]
+    fn test_sensor_reading_with_offset(
+        #[case] distance: u16,
+        #[case] offset: i16,
+        #[case] expected_distance: u16,
+    ) {
+        let reading = SensorReading {
+            distance,
+            signal_strength: 100,
+            temperature: 32.5,
+            timestamp: 0,
+            error: 0,
+        };
+        let corrected = reading.with_offset(offset);
+        assert_eq!(corrected.distance, expected_distance);
+        assert_eq!(corrected.signal_strength, reading.signal_strength);
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[cfg(feature = "trace")]
+    #[test]
+    fn test_trace_blocking() {
+        use embedded_tfluna::i2c::TraceEvent;
+        use std::sync::Mutex;
+
+        static EVENTS: Mutex<Vec<(bool, u8, Vec<u8>)>> = Mutex::new(Vec::new());
+
+        fn tracer(event: &TraceEvent) {
+            let entry = match event {
+                TraceEvent::Read { register, data } => (false, *register, Vec::from(*data)),
+                TraceEvent::Write { register, data } => (true, *register, Vec::from(*data)),
+            };
+            EVENTS.lock().unwrap().push(entry);
+        }
+
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x25, &[1]),
+            Transaction::Read(0x0A, &[0, 1, 2]),
+            Transaction::Write(0x25, &[0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_trace(tracer);
+        device.enable().unwrap();
+        device.get_firmware_version().unwrap();
+        device.clear_trace();
+        device.disable().unwrap();
+        assert_eq!(
+            *EVENTS.lock().unwrap(),
+            Vec::from([
+                (true, 0x25, Vec::from([1])),
+                (false, 0x0A, Vec::from([0, 1, 2])),
+            ])
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    /// Two devices sharing one bus are triggered back-to-back and only then read.
+    #[test]
+    fn test_synchronized_trigger_two_devices_blocking() {
+        let first_address = DEFAULT_SLAVE_ADDRESS;
+        let second_address = 0x11;
+        let mut i2c = I2cTraitMock::new(&[
+            I2cTraitTransaction::write(first_address, Vec::from([0x24, 1])),
+            I2cTraitTransaction::write(second_address, Vec::from([0x24, 1])),
+            I2cTraitTransaction::write_read(
+                first_address,
+                Vec::from([0x00]),
+                Vec::from([10, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            ),
+            I2cTraitTransaction::write_read(
+                second_address,
+                Vec::from([0x00]),
+                Vec::from([20, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            ),
+        ]);
+        let mut first =
+            TFLunaBlocking::new(i2c.clone(), Address::from(first_address), Delay {}).unwrap();
+        let mut second =
+            TFLunaBlocking::new(i2c.clone(), Address::from(second_address), Delay {}).unwrap();
+
+        first.trigger_measurement().unwrap();
+        second.trigger_measurement().unwrap();
+        let first_measurement = first.read_triggered().unwrap();
+        let second_measurement = second.read_triggered().unwrap();
+
+        assert_eq!(first_measurement.distance, 10);
+        assert_eq!(second_measurement.distance, 20);
+        assert_eq!(first_measurement.timestamp, second_measurement.timestamp);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_synchronized_trigger_two_devices_async() {
+        let first_address = DEFAULT_SLAVE_ADDRESS;
+        let second_address = 0x11;
+        let mut i2c = I2cTraitMock::new(&[
+            I2cTraitTransaction::write(first_address, Vec::from([0x24, 1])),
+            I2cTraitTransaction::write(second_address, Vec::from([0x24, 1])),
+            I2cTraitTransaction::write_read(
+                first_address,
+                Vec::from([0x00]),
+                Vec::from([10, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            ),
+            I2cTraitTransaction::write_read(
+                second_address,
+                Vec::from([0x00]),
+                Vec::from([20, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            ),
+        ]);
+        let mut first =
+            TFLunaAsync::new(i2c.clone(), Address::from(first_address), Delay {}).unwrap();
+        let mut second =
+            TFLunaAsync::new(i2c.clone(), Address::from(second_address), Delay {}).unwrap();
+
+        first.trigger_measurement().await.unwrap();
+        second.trigger_measurement().await.unwrap();
+        let first_measurement = first.read_triggered().await.unwrap();
+        let second_measurement = second.read_triggered().await.unwrap();
+
+        assert_eq!(first_measurement.distance, 10);
+        assert_eq!(second_measurement.distance, 20);
+        assert_eq!(first_measurement.timestamp, second_measurement.timestamp);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_change_slave_address_blocking() {
+        let new_address = 0x11;
+        let mut i2c = I2cTraitMock::new(&[
+            I2cTraitTransaction::write(DEFAULT_SLAVE_ADDRESS, Vec::from([0x22, new_address])),
+            I2cTraitTransaction::write(DEFAULT_SLAVE_ADDRESS, Vec::from([0x20, 1])),
+            I2cTraitTransaction::write(DEFAULT_SLAVE_ADDRESS, Vec::from([0x21, 2])),
+            I2cTraitTransaction::write_read(
+                new_address,
+                Vec::from([0x22]),
+                Vec::from([new_address]),
+            ),
+        ]);
+        let mut device = device_blocking(&mut i2c);
+        let pending = device.set_slave_address(new_address).unwrap();
+        assert_eq!(pending.address(), Address::from(new_address));
+        device.commit_address_change(pending).unwrap();
+        assert_eq!(device.get_slave_address().unwrap(), new_address);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_change_slave_address_async() {
+        let new_address = 0x11;
+        let mut i2c = I2cTraitMock::new(&[
+            I2cTraitTransaction::write(DEFAULT_SLAVE_ADDRESS, Vec::from([0x22, new_address])),
+            I2cTraitTransaction::write(DEFAULT_SLAVE_ADDRESS, Vec::from([0x20, 1])),
+            I2cTraitTransaction::write(DEFAULT_SLAVE_ADDRESS, Vec::from([0x21, 2])),
+            I2cTraitTransaction::write_read(
+                new_address,
+                Vec::from([0x22]),
+                Vec::from([new_address]),
+            ),
+        ]);
+        let mut device = device_async(&mut i2c);
+        let pending = device.set_slave_address(new_address).await.unwrap();
+        assert_eq!(pending.address(), Address::from(new_address));
+        device.commit_address_change(pending).await.unwrap();
+        assert_eq!(device.get_slave_address().await.unwrap(), new_address);
+        i2c.done();
+    }
+
+    #[cfg(feature = "uom")]
+    #[test]
+    fn test_sensor_reading_uom() {
+        use uom::si::length::meter;
+        use uom::si::thermodynamic_temperature::degree_celsius;
+
+        let reading = SensorReading {
+            distance: 150,
+            signal_strength: 100,
+            temperature: 32.5,
+            timestamp: 0,
+            error: 0,
+        };
+        assert!((reading.length().get::<meter>() - 1.5).abs() < 1e-6);
+        assert!((reading.temperature_uom().get::<degree_celsius>() - 32.5).abs() < 1e-3);
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(5)]
+    #[case(10)]
+    #[case(20)]
+    #[case(25)]
+    #[case(50)]
+    #[case(100)]
+    #[case(125)]
+    #[case(166)]
+    #[case(200)]
+    #[case(250)]
+    fn test_valid_framerate_blocking(#[case] framerate: u16) {
+        let [low_byte, high_byte] = framerate.to_le_bytes();
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Write(
+            0x26,
+            &[low_byte, high_byte],
+        )]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_framerate(framerate).unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_framerate_options_match_validation() {
+        let options = TFLunaBlocking::<I2cTraitMock, Delay>::framerate_options();
+        assert!(options.windows(2).all(|pair| pair[0] < pair[1]));
+        let expectations = options
+            .iter()
+            .map(|framerate| {
+                let [low_byte, high_byte] = framerate.to_le_bytes();
+                I2cTraitTransaction::write(
+                    DEFAULT_SLAVE_ADDRESS,
+                    Vec::from([0x26, low_byte, high_byte]),
+                )
+            })
+            .collect::<Vec<I2cTraitTransaction>>();
+        let mut i2c = I2cTraitMock::new(&expectations);
+        let mut device = device_blocking(&mut i2c);
+        for framerate in 1..=1000 {
+            assert_eq!(
+                device.set_framerate(framerate).is_ok(),
+                options.contains(&framerate),
+                "framerate {framerate}"
+            );
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::firmware_3_5_1(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x0A, &[1, 5, 3]),
+    ])), false)]
+    #[case::firmware_3_6_0(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x0A, &[0, 6, 3]),
+    ])), true)]
+    fn test_ultra_low_power_reliable_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected: bool,
+    ) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.ultra_low_power_reliable().unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::firmware_3_5_1(&mut i2c_async(Vec::from([
+        Transaction::Read(0x0A, &[1, 5, 3]),
+    ])), false)]
+    #[tokio::test]
+    #[case::firmware_3_6_0(&mut i2c_async(Vec::from([
+        Transaction::Read(0x0A, &[0, 6, 3]),
+    ])), true)]
+    async fn test_ultra_low_power_reliable_async(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected: bool,
+    ) {
+        let mut device = device_async(i2c);
+        assert_eq!(device.ultra_low_power_reliable().await.unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::ultra_low(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x1F, &[1]),
+        Transaction::Write(0x20, &[1]),
+        Transaction::Write(0x21, &[2]),
+    ])), PowerMode::UltraLow)]
+    #[case::normal(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[0]),
+        Transaction::Write(0x1F, &[0]),
+        Transaction::Write(0x20, &[1]),
+        Transaction::Write(0x21, &[2]),
+        Transaction::Write(0x28, &[0]),
+    ])), PowerMode::Normal)]
+    #[case::power_saving(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[0]),
+        Transaction::Write(0x1F, &[0]),
+        Transaction::Write(0x20, &[1]),
+        Transaction::Write(0x21, &[2]),
+        Transaction::Write(0x28, &[1]),
+    ])), PowerMode::PowerSaving)]
+    fn test_set_power_mode_blocking(#[case] i2c: &mut I2cTraitMock, #[case] mode: PowerMode) {
+        let mut device = device_blocking(i2c);
+        assert!(device.set_power_mode(mode).is_ok());
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::ultra_low(&mut i2c_async(Vec::from([
+        Transaction::Write(0x1F, &[1]),
+        Transaction::Write(0x20, &[1]),
+        Transaction::Write(0x21, &[2]),
+    ])), PowerMode::UltraLow)]
+    #[tokio::test]
+    #[case::normal(&mut i2c_async(Vec::from([
+        Transaction::Read(0x00, &[0]),
+        Transaction::Write(0x1F, &[0]),
+        Transaction::Write(0x20, &[1]),
+        Transaction::Write(0x21, &[2]),
+        Transaction::Write(0x28, &[0]),
+    ])), PowerMode::Normal)]
+    #[tokio::test]
+    #[case::power_saving(&mut i2c_async(Vec::from([
+        Transaction::Read(0x00, &[0]),
+        Transaction::Write(0x1F, &[0]),
+        Transaction::Write(0x20, &[1]),
+        Transaction::Write(0x21, &[2]),
+        Transaction::Write(0x28, &[1]),
+    ])), PowerMode::PowerSaving)]
+    async fn test_set_power_mode_async(#[case] i2c: &mut I2cTraitMock, #[case] mode: PowerMode) {
+        let mut device = device_async(i2c);
+        assert!(device.set_power_mode(mode).await.is_ok());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::close(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[30, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+    ])), true)]
+    #[case::far(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[80, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+    ])), false)]
+    #[case::weak_signal(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[30, 0, 0x0A, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+    ])), false)]
+    #[case::saturated(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[30, 0, 0xFF, 0xFF, 0xB2, 0x0C, 0, 0, 0, 0]),
+    ])), false)]
+    fn test_proximity_switch_blocking(#[case] i2c: &mut I2cTraitMock, #[case] expected: bool) {
+        let mut switch = ProximitySwitchBlocking::new(device_blocking(i2c), 50);
+        assert_eq!(switch.poll().unwrap(), expected);
+        switch.release();
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::close(&mut i2c_async(Vec::from([
+        Transaction::Read(0x00, &[30, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+    ])), true)]
+    #[tokio::test]
+    #[case::far(&mut i2c_async(Vec::from([
+        Transaction::Read(0x00, &[80, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+    ])), false)]
+    async fn test_proximity_switch_async(#[case] i2c: &mut I2cTraitMock, #[case] expected: bool) {
+        let mut switch = ProximitySwitchAsync::new(device_async(i2c), 50);
+        assert_eq!(switch.poll().await.unwrap(), expected);
+        switch.release();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::framerate(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x26, &[0xFA, 0]),
+        Transaction::Read(0x26, &[0xFA, 0]),
+    ])), ConfigWord::Framerate, 250)]
+    #[case::signal_strength_threshold(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x2A, &[0x58, 0x02]),
+        Transaction::Read(0x2A, &[0x58, 0x02]),
+    ])), ConfigWord::SignalStrengthThreshold, 600)]
+    #[case::dummy_distance(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x2C, &[66, 0]),
+        Transaction::Read(0x2C, &[66, 0]),
+    ])), ConfigWord::DummyDistance, 66)]
+    #[case::minimum_distance(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x2E, &[20, 0]),
+        Transaction::Read(0x2E, &[20, 0]),
+    ])), ConfigWord::MinimumDistance, 20)]
+    #[case::maximum_distance(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x30, &[0xD0, 0x07]),
+        Transaction::Read(0x30, &[0xD0, 0x07]),
+    ])), ConfigWord::MaximumDistance, 2000)]
+    fn test_config_word_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] field: ConfigWord,
+        #[case] value: u16,
+    ) {
+        let mut device = device_blocking(i2c);
+        assert!(device.set_config_word(field, value).is_ok());
+        assert_eq!(device.get_config_word(field).unwrap(), value);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::framerate(&mut i2c_async(Vec::from([
+        Transaction::Write(0x26, &[0xFA, 0]),
+        Transaction::Read(0x26, &[0xFA, 0]),
+    ])), ConfigWord::Framerate, 250)]
+    #[tokio::test]
+    #[case::maximum_distance(&mut i2c_async(Vec::from([
+        Transaction::Write(0x30, &[0xD0, 0x07]),
+        Transaction::Read(0x30, &[0xD0, 0x07]),
+    ])), ConfigWord::MaximumDistance, 2000)]
+    async fn test_config_word_async(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] field: ConfigWord,
+        #[case] value: u16,
+    ) {
+        let mut device = device_async(i2c);
+        assert!(device.set_config_word(field, value).await.is_ok());
+        assert_eq!(device.get_config_word(field).await.unwrap(), value);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_config_word_invalid_framerate_blocking() {
+        let mut i2c = i2c_blocking(Vec::new());
+        let mut device = device_blocking(&mut i2c);
+        assert!(device.set_config_word(ConfigWord::Framerate, 240).is_err());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::responds_after_retries(&mut i2c_blocking(Vec::from([
+        Transaction::WriteError(0x21, &[2], NACK),
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::Read(0x00, &[0]),
+    ])), true)]
+    #[case::unresponsive(&mut i2c_blocking(Vec::from([
+        Transaction::WriteError(0x21, &[2], NACK),
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::ReadError(0x00, &[0], NACK),
+    ])), false)]
+    fn test_recover_blocking(#[case] i2c: &mut I2cTraitMock, #[case] expected_recovered: bool) {
+        let mut device = device_blocking(i2c);
+        let result = device.recover();
+        if expected_recovered {
+            assert!(result.is_ok(), "{:?}", result);
+        } else {
+            assert!(matches!(result, Err(Error::Timeout)), "{:?}", result);
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::responds_after_retries(&mut i2c_async(Vec::from([
+        Transaction::WriteError(0x21, &[2], NACK),
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::Read(0x00, &[0]),
+    ])), true)]
+    async fn test_recover_async(#[case] i2c: &mut I2cTraitMock, #[case] expected_recovered: bool) {
+        let mut device = device_async(i2c);
+        let result = device.recover().await;
+        assert_eq!(result.is_ok(), expected_recovered, "{:?}", result);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_measurement_stream_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::Read(0x00, &[11, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        let mut stream = device.stream(1);
+        assert_eq!(stream.next_measurement().unwrap().distance, 10);
+        assert_eq!(stream.next_measurement().unwrap().distance, 11);
+        assert!(!stream.is_resync_pending());
+        i2c.done();
+    }
+
+    /// I2C bus whose first write-read stays pending once, so that it can be cancelled.
+    #[cfg(feature = "async")]
+    struct PendingOnceI2c {
+        inner: I2cTraitMock,
+        pending: bool,
+    }
+
+    #[cfg(feature = "async")]
+    impl embedded_hal::i2c::ErrorType for PendingOnceI2c {
+        type Error = ErrorKind;
+    }
+
+    #[cfg(feature = "async")]
+    impl embedded_hal_async::i2c::I2c for PendingOnceI2c {
+        async fn write_read(
+            &mut self,
+            address: u8,
+            write: &[u8],
+            read: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            if self.pending {
+                self.pending = false;
+                let mut yielded = false;
+                core::future::poll_fn(|_| {
+                    if yielded {
+                        core::task::Poll::Ready(())
+                    } else {
+                        yielded = true;
+                        core::task::Poll::Pending
+                    }
+                })
+                .await;
+            }
+            embedded_hal_async::i2c::I2c::write_read(&mut self.inner, address, write, read).await
+        }
+
+        async fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [embedded_hal::i2c::Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            embedded_hal_async::i2c::I2c::transaction(&mut self.inner, address, operations).await
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_measurement_stream_cancelled_read_async() {
+        use core::future::Future;
+        use core::task::{Context, Poll, Waker};
+
+        let mut i2c = i2c_async(Vec::from([
+            // Resynchronization read, discarded
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::Read(0x00, &[11, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+        ]));
+        let bus = PendingOnceI2c {
+            inner: i2c.clone(),
+            pending: true,
+        };
+        let mut device = TFLunaAsync::new(bus, Address::default(), Delay {}).unwrap();
+        {
+            let mut stream = device.stream(1);
+            {
+                let mut next = core::pin::pin!(stream.next_measurement());
+                let poll = next.as_mut().poll(&mut Context::from_waker(Waker::noop()));
+                assert!(matches!(poll, Poll::Pending));
+                // The future is dropped here, in the middle of the read
+            }
+            assert!(stream.is_resync_pending());
+            // The stream itself is dropped as well
+        }
+        let mut stream = device.stream(1);
+        assert!(stream.is_resync_pending());
+        let measurement = stream.next_measurement().await.unwrap();
+        assert_eq!(measurement.distance, 11);
+        assert!(!stream.is_resync_pending());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::idle_then_resume(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x23, &[1]),
+        Transaction::Write(0x23, &[0]),
+    ])))]
+    fn test_idle_resume_blocking(#[case] i2c: &mut I2cTraitMock) {
+        let mut device = device_blocking(i2c);
+        assert!(device.set_idle().is_ok());
+        assert!(device.resume().is_ok());
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::idle_then_resume(&mut i2c_async(Vec::from([
+        Transaction::Write(0x23, &[1]),
+        Transaction::Write(0x23, &[0]),
+    ])))]
+    async fn test_idle_resume_async(#[case] i2c: &mut I2cTraitMock) {
+        let mut device = device_async(i2c);
+        assert!(device.set_idle().await.is_ok());
+        assert!(device.resume().await.is_ok());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::wrap_around(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x06, &[0xF0, 0xFF]),
+        Transaction::Read(0x06, &[0x10, 0x00]),
+        Transaction::Read(0x06, &[0x1A, 0x00]),
+    ])), [0, 0x20, 0x0A])]
+    fn test_ticks_since_last_read_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected_ticks: [u16; 3],
+    ) {
+        let mut device = device_blocking(i2c);
+        for expected in expected_ticks {
+            assert_eq!(device.ticks_since_last_read().unwrap(), expected);
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::wrap_around(&mut i2c_async(Vec::from([
+        Transaction::Read(0x06, &[0xF0, 0xFF]),
+        Transaction::Read(0x06, &[0x10, 0x00]),
+        Transaction::Read(0x06, &[0x1A, 0x00]),
+    ])), [0, 0x20, 0x0A])]
+    async fn test_ticks_since_last_read_async(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected_ticks: [u16; 3],
+    ) {
+        let mut device = device_async(i2c);
+        for expected in expected_ticks {
+            assert_eq!(device.ticks_since_last_read().await.unwrap(), expected);
+        }
+        i2c.done();
+    }
+
+    #[rstest]
+    #[case::address_nack(Error::I2c(NACK), RecoveryHint::WakeDevice)]
+    #[case::data_nack(
+        Error::I2c(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data)),
+        RecoveryHint::RetryLater
+    )]
+    #[case::arbitration_loss(Error::I2c(ErrorKind::ArbitrationLoss), RecoveryHint::RetryLater)]
+    #[case::bus_error(Error::I2c(ErrorKind::Bus), RecoveryHint::CheckWiring)]
+    #[case::timeout(Error::Timeout, RecoveryHint::RetryLater)]
+    #[case::not_found(Error::NotFound, RecoveryHint::CheckWiring)]
+    #[case::invalid_data(Error::InvalidData(0xFF), RecoveryHint::RetryLater)]
+    #[case::invalid_parameter(Error::InvalidParameter, RecoveryHint::None)]
+    #[case::ultra_low_power_active(Error::UltraLowPowerActive, RecoveryHint::WakeDevice)]
+    #[case::no_valid_samples(Error::NoValidSamples, RecoveryHint::RetryLater)]
+    #[case::pin(Error::Pin, RecoveryHint::None)]
+    #[case::other(Error::Other, RecoveryHint::None)]
+    fn test_error_recovery_hint(#[case] error: Error<ErrorKind>, #[case] expected: RecoveryHint) {
+        assert_eq!(error.recovery_hint(), expected);
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::framerate_100(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x23, &[0]),
+        Transaction::Write(0x26, &[100, 0]),
+        Transaction::Write(0x25, &[1]),
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+    ])), 100)]
+    fn test_start_continuous_blocking(#[case] i2c: &mut I2cTraitMock, #[case] framerate: u16) {
+        let mut device = device_blocking(i2c);
+        let measurement = device.start_continuous(framerate);
+        assert!(measurement.is_ok(), "{:?}", measurement);
+        assert_eq!(measurement.unwrap().distance, 10);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::framerate_100(&mut i2c_async(Vec::from([
+        Transaction::Write(0x23, &[0]),
+        Transaction::Write(0x26, &[100, 0]),
+        Transaction::Write(0x25, &[1]),
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+    ])), 100)]
+    async fn test_start_continuous_async(#[case] i2c: &mut I2cTraitMock, #[case] framerate: u16) {
+        let mut device = device_async(i2c);
+        let measurement = device.start_continuous(framerate).await;
+        assert!(measurement.is_ok(), "{:?}", measurement);
+        assert_eq!(measurement.unwrap().distance, 10);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::framerate_0(&mut i2c_blocking(Vec::new()), 0)]
+    #[case::framerate_240(&mut i2c_blocking(Vec::new()), 240)]
+    fn test_start_continuous_invalid_framerate_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] framerate: u16,
+    ) {
+        let mut device = device_blocking(i2c);
+        assert!(matches!(
+            device.start_continuous(framerate),
+            Err(Error::InvalidParameter)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    /// Identity can be read from a device that was never enabled, i.e. without any write.
+    #[rstest]
+    #[case::not_enabled(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x3C, b"LUNA"),
+        Transaction::Read(0x0A, &[1, 5, 3]),
+        Transaction::Read(0x10, b"T3300245010082"),
+    ])))]
+    fn test_read_identity_without_enable_blocking(#[case] i2c: &mut I2cTraitMock) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.get_signature().unwrap(), Signature(*b"LUNA"));
+        assert_eq!(
+            device.get_firmware_version().unwrap(),
+            FirmwareVersion {
+                major: 3,
+                minor: 5,
+                revision: 1
+            }
+        );
+        assert_eq!(
+            device.get_serial_number().unwrap(),
+            SerialNumber(*b"T3300245010082")
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::not_enabled(&mut i2c_async(Vec::from([
+        Transaction::Read(0x3C, b"LUNA"),
+        Transaction::Read(0x0A, &[1, 5, 3]),
+        Transaction::Read(0x10, b"T3300245010082"),
+    ])))]
+    async fn test_read_identity_without_enable_async(#[case] i2c: &mut I2cTraitMock) {
+        let mut device = device_async(i2c);
+        assert_eq!(device.get_signature().await.unwrap(), Signature(*b"LUNA"));
+        assert_eq!(
+            device.get_firmware_version().await.unwrap(),
+            FirmwareVersion {
+                major: 3,
+                minor: 5,
+                revision: 1
+            }
+        );
+        assert_eq!(
+            device.get_serial_number().await.unwrap(),
+            SerialNumber(*b"T3300245010082")
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::factory_defaults(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x29, &[1]),
+        Transaction::Write(0x21, &[2]),
+        Transaction::Write(0x25, &[1]),
+    ])), Startup::FactoryDefaults)]
+    #[case::saved_settings(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x25, &[1]),
+    ])), Startup::SavedSettings)]
+    fn test_start_blocking(#[case] i2c: &mut I2cTraitMock, #[case] startup: Startup) {
+        let mut device = device_blocking(i2c);
+        assert!(device.start(startup).is_ok());
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::factory_defaults(&mut i2c_async(Vec::from([
+        Transaction::Write(0x29, &[1]),
+        Transaction::Write(0x21, &[2]),
+        Transaction::Write(0x25, &[1]),
+    ])), Startup::FactoryDefaults)]
+    #[tokio::test]
+    #[case::saved_settings(&mut i2c_async(Vec::from([
+        Transaction::Write(0x25, &[1]),
+    ])), Startup::SavedSettings)]
+    async fn test_start_async(#[case] i2c: &mut I2cTraitMock, #[case] startup: Startup) {
+        let mut device = device_async(i2c);
+        assert!(device.start(startup).await.is_ok());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::normal(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x28, &[0]),
+        Transaction::Read(0x26, &[100, 0]),
+    ])), 100)]
+    #[case::power_saving_capped(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x28, &[1]),
+        Transaction::Read(0x26, &[100, 0]),
+    ])), 10)]
+    #[case::power_saving_below_cap(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x28, &[1]),
+        Transaction::Read(0x26, &[5, 0]),
+    ])), 5)]
+    #[case::ultra_low(&mut i2c_blocking(Vec::from([
+        Transaction::ReadError(0x28, &[0], NACK),
+        Transaction::Read(0x3C, b"LUNA"),
+    ])), 0)]
+    fn test_get_effective_framerate_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected_framerate: u16,
+    ) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(
+            device.get_effective_framerate().unwrap(),
+            expected_framerate
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::power_saving_capped(&mut i2c_async(Vec::from([
+        Transaction::Read(0x28, &[1]),
+        Transaction::Read(0x26, &[100, 0]),
+    ])), 10)]
+    async fn test_get_effective_framerate_async(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected_framerate: u16,
+    ) {
+        let mut device = device_async(i2c);
+        assert_eq!(
+            device.get_effective_framerate().await.unwrap(),
+            expected_framerate
+        );
+        i2c.done();
+    }
+
+    const PROVISIONED_CONFIG: Config = Config {
+        framerate: 100,
+        ranging_mode: RangingMode::Continuous,
+        power_mode: PowerMode::Normal,
+        signal_strength_threshold: 100,
+        dummy_distance: 0,
+        minimum_distance: 0,
+        maximum_distance: 800,
+    };
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::provisioned(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x22, &[DEFAULT_SLAVE_ADDRESS]),
+        Transaction::Read(0x3C, b"LUNA"),
+        Transaction::Read(0x0A, &[1, 5, 3]),
+        Transaction::Read(0x26, &[100, 0]),
+        Transaction::Read(0x23, &[0]),
+        Transaction::Read(0x2A, &[100, 0]),
+        Transaction::Read(0x2C, &[0, 0]),
+        Transaction::Read(0x2E, &[0, 0]),
+        Transaction::Read(0x30, &[0x20, 0x03]),
+        Transaction::Read(0x28, &[0]),
+    ])), DEFAULT_SLAVE_ADDRESS, None)]
+    #[case::wrong_address(&mut i2c_blocking(Vec::new()), 0x11, Some(ProvisioningCheck::Address))]
+    #[case::wrong_signature(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x22, &[DEFAULT_SLAVE_ADDRESS]),
+        Transaction::Read(0x3C, b"LUNB"),
+    ])), DEFAULT_SLAVE_ADDRESS, Some(ProvisioningCheck::Signature))]
+    #[case::blank_firmware(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x22, &[DEFAULT_SLAVE_ADDRESS]),
+        Transaction::Read(0x3C, b"LUNA"),
+        Transaction::Read(0x0A, &[0xFF, 0xFF, 0xFF]),
+    ])), DEFAULT_SLAVE_ADDRESS, Some(ProvisioningCheck::FirmwareVersion))]
+    #[case::wrong_maximum_distance(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x22, &[DEFAULT_SLAVE_ADDRESS]),
+        Transaction::Read(0x3C, b"LUNA"),
+        Transaction::Read(0x0A, &[1, 5, 3]),
+        Transaction::Read(0x26, &[100, 0]),
+        Transaction::Read(0x23, &[0]),
+        Transaction::Read(0x2A, &[100, 0]),
+        Transaction::Read(0x2C, &[0, 0]),
+        Transaction::Read(0x2E, &[0, 0]),
+        Transaction::Read(0x30, &[0xC4, 0x09]),
+        Transaction::Read(0x28, &[0]),
+    ])), DEFAULT_SLAVE_ADDRESS, Some(ProvisioningCheck::MaximumDistance))]
+    fn test_verify_provisioning_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected_address: u8,
+        #[case] expected_mismatch: Option<ProvisioningCheck>,
+    ) {
+        let mut device = device_blocking(i2c);
+        let result = device.verify_provisioning(&PROVISIONED_CONFIG, expected_address);
+        match expected_mismatch {
+            None => assert!(result.is_ok(), "{:?}", result),
+            Some(check) => assert!(
+                matches!(result, Err(Error::ProvisioningMismatch(c)) if c == check),
+                "{:?}",
+                result
+            ),
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::provisioned(&mut i2c_async(Vec::from([
+        Transaction::Read(0x22, &[DEFAULT_SLAVE_ADDRESS]),
+        Transaction::Read(0x3C, b"LUNA"),
+        Transaction::Read(0x0A, &[1, 5, 3]),
+        Transaction::Read(0x26, &[100, 0]),
+        Transaction::Read(0x23, &[0]),
+        Transaction::Read(0x2A, &[100, 0]),
+        Transaction::Read(0x2C, &[0, 0]),
+        Transaction::Read(0x2E, &[0, 0]),
+        Transaction::Read(0x30, &[0x20, 0x03]),
+        Transaction::Read(0x28, &[0]),
+    ])), None)]
+    #[tokio::test]
+    #[case::wrong_signature(&mut i2c_async(Vec::from([
+        Transaction::Read(0x22, &[DEFAULT_SLAVE_ADDRESS]),
+        Transaction::Read(0x3C, b"LUNB"),
+    ])), Some(ProvisioningCheck::Signature))]
+    async fn test_verify_provisioning_async(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected_mismatch: Option<ProvisioningCheck>,
+    ) {
+        let mut device = device_async(i2c);
+        let result = device
+            .verify_provisioning(&PROVISIONED_CONFIG, DEFAULT_SLAVE_ADDRESS)
+            .await;
+        match expected_mismatch {
+            None => assert!(result.is_ok(), "{:?}", result),
+            Some(check) => assert!(
+                matches!(result, Err(Error::ProvisioningMismatch(c)) if c == check),
+                "{:?}",
+                result
+            ),
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[cfg(feature = "nb")]
+    #[test]
+    fn test_try_get_measurement_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Read(
+            0x00,
+            &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0],
+        )]));
+        let mut pin = PinMock::new(&[
+            PinTransaction::get(PinState::Low),
+            PinTransaction::get(PinState::High),
+            PinTransaction::get(PinState::Low),
+        ]);
+        let mut device = device_blocking(&mut i2c).with_data_ready(&mut pin);
+        assert!(matches!(
+            device.try_get_measurement(),
+            Err(nb::Error::WouldBlock)
+        ));
+        assert_eq!(device.try_get_measurement().unwrap().distance, 10);
+        // Calling again after completion waits for the next frame
+        assert!(matches!(
+            device.try_get_measurement(),
+            Err(nb::Error::WouldBlock)
+        ));
+        pin.done();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[cfg(feature = "nb")]
+    #[test]
+    fn test_try_get_measurement_pin_error_blocking() {
+        let mut i2c = i2c_blocking(Vec::new());
+        let mut pin = PinMock::new(&[PinTransaction::get(PinState::High)
+            .with_error(PinMockError::Io(std::io::ErrorKind::Other))]);
+        let mut device = device_blocking(&mut i2c).with_data_ready(&mut pin);
+        assert!(matches!(
+            device.try_get_measurement(),
+            Err(nb::Error::Other(Error::Pin))
+        ));
+        pin.done();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::defaults(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x2A, &[100, 0]),
+        Transaction::Read(0x2C, &[0, 0]),
+    ])), (100, 0))]
+    #[case::custom(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x2A, &[0x58, 0x02]),
+        Transaction::Read(0x2C, &[66, 0]),
+    ])), (600, 66))]
+    fn test_get_dummy_behavior_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected: (u16, u16),
+    ) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.get_dummy_behavior().unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::custom(&mut i2c_async(Vec::from([
+        Transaction::Read(0x2A, &[0x58, 0x02]),
+        Transaction::Read(0x2C, &[66, 0]),
+    ])), (600, 66))]
+    async fn test_get_dummy_behavior_async(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected: (u16, u16),
+    ) {
+        let mut device = device_async(i2c);
+        assert_eq!(device.get_dummy_behavior().await.unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::positive(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x04, &[0xB2, 0x0C]),
+    ])), (0x0CB2, 32.5))]
+    #[case::negative(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x04, &[0x0C, 0xFE]),
+    ])), (0xFE0C, -5.0))]
+    fn test_get_temperature_detailed_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected: (u16, f32),
+    ) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.get_temperature_detailed().unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::negative(&mut i2c_async(Vec::from([
+        Transaction::Read(0x04, &[0x0C, 0xFE]),
+    ])), (0xFE0C, -5.0))]
+    async fn test_get_temperature_detailed_async(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] expected: (u16, f32),
+    ) {
+        let mut device = device_async(i2c);
+        assert_eq!(device.get_temperature_detailed().await.unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::hold_last(LossPolicy::HoldLast, [80, 80, 70])]
+    #[case::dummy(LossPolicy::Dummy(1200), [80, 1200, 70])]
+    #[case::zero(LossPolicy::Zero, [80, 0, 70])]
+    fn test_get_measurement_with_policy_blocking(
+        #[case] policy: LossPolicy,
+        #[case] expected_distances: [u16; 3],
+    ) {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x00, &[80, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+            Transaction::Read(0x00, &[5, 0, 0x0A, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+            Transaction::Read(0x00, &[70, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        for expected in expected_distances {
+            let measurement = device.get_measurement_with_policy(policy).unwrap();
+            assert_eq!(measurement.distance, expected);
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_measurement_with_policy_hold_without_valid_reading_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Read(
+            0x00,
+            &[5, 0, 0xFF, 0xFF, 0xB2, 0x0C, 0, 0, 0, 0],
+        )]));
+        let mut device = device_blocking(&mut i2c);
+        let measurement = device
+            .get_measurement_with_policy(LossPolicy::HoldLast)
+            .unwrap();
+        assert_eq!(measurement.distance, 0);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::hold_last(LossPolicy::HoldLast, [80, 80, 70])]
+    #[tokio::test]
+    #[case::dummy(LossPolicy::Dummy(1200), [80, 1200, 70])]
+    #[tokio::test]
+    #[case::zero(LossPolicy::Zero, [80, 0, 70])]
+    async fn test_get_measurement_with_policy_async(
+        #[case] policy: LossPolicy,
+        #[case] expected_distances: [u16; 3],
+    ) {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[80, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+            Transaction::Read(0x00, &[5, 0, 0x0A, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+            Transaction::Read(0x00, &[70, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        for expected in expected_distances {
+            let measurement = device.get_measurement_with_policy(policy).await.unwrap();
+            assert_eq!(measurement.distance, expected);
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::factory_defaults(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x29, &[1]),
+        Transaction::Write(0x21, &[2]),
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::Read(0x00, &[0]),
+        Transaction::Write(0x25, &[1]),
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0x10, 0, 0, 0]),
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0x10, 0, 0, 0]),
+        Transaction::Read(0x00, &[12, 0, 0x64, 0, 0xB2, 0x0C, 0x1A, 0, 0, 0]),
+    ])), Startup::FactoryDefaults)]
+    #[case::saved_settings(&mut i2c_blocking(Vec::from([
+        Transaction::Write(0x25, &[1]),
+        Transaction::Read(0x23, &[0]),
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0x10, 0, 0, 0]),
+        Transaction::Read(0x00, &[12, 0, 0x64, 0, 0xB2, 0x0C, 0x1A, 0, 0, 0]),
+    ])), Startup::SavedSettings)]
+    fn test_init_default_blocking(#[case] i2c: &mut I2cTraitMock, #[case] startup: Startup) {
+        let mut device = device_blocking(i2c);
+        let measurement = device.init_default(startup);
+        assert!(measurement.is_ok(), "{:?}", measurement);
+        assert_eq!(measurement.unwrap().distance, 12);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::factory_defaults(&mut i2c_async(Vec::from([
+        Transaction::Write(0x29, &[1]),
+        Transaction::Write(0x21, &[2]),
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::Read(0x00, &[0]),
+        Transaction::Write(0x25, &[1]),
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0x10, 0, 0, 0]),
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0x10, 0, 0, 0]),
+        Transaction::Read(0x00, &[12, 0, 0x64, 0, 0xB2, 0x0C, 0x1A, 0, 0, 0]),
+    ])), Startup::FactoryDefaults)]
+    #[tokio::test]
+    #[case::saved_settings(&mut i2c_async(Vec::from([
+        Transaction::Write(0x25, &[1]),
+        Transaction::Read(0x23, &[0]),
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0x10, 0, 0, 0]),
+        Transaction::Read(0x00, &[12, 0, 0x64, 0, 0xB2, 0x0C, 0x1A, 0, 0, 0]),
+    ])), Startup::SavedSettings)]
+    async fn test_init_default_async(#[case] i2c: &mut I2cTraitMock, #[case] startup: Startup) {
+        let mut device = device_async(i2c);
+        let measurement = device.init_default(startup).await;
+        assert!(measurement.is_ok(), "{:?}", measurement);
+        assert_eq!(measurement.unwrap().distance, 12);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_init_default_trigger_mode_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x25, &[1]),
+            Transaction::Read(0x23, &[1]),
+            Transaction::Read(0x26, &[100, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[12, 0, 0x64, 0, 0xB2, 0x0C, 0x1A, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        let measurement = device.init_default(Startup::SavedSettings);
+        assert!(measurement.is_ok(), "{:?}", measurement);
+        assert_eq!(measurement.unwrap().distance, 12);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_init_default_trigger_mode_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Write(0x25, &[1]),
+            Transaction::Read(0x23, &[1]),
+            Transaction::Read(0x26, &[100, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[12, 0, 0x64, 0, 0xB2, 0x0C, 0x1A, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        let measurement = device.init_default(Startup::SavedSettings).await;
+        assert!(measurement.is_ok(), "{:?}", measurement);
+        assert_eq!(measurement.unwrap().distance, 12);
+        i2c.done();
+    }
+
+    const RETRY_CONFIG: RetryConfig = RetryConfig {
+        nack_retries: 2,
+        nack_delay_ms: 1,
+        arbitration_loss_retries: 1,
+        arbitration_loss_delay_ms: 1,
+        invalid_data_retries: 1,
+        invalid_data_delay_ms: 1,
+    };
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_retries_recover_from_transient_nack_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::ReadError(0x26, &[0, 0], NACK),
+            Transaction::ReadError(0x26, &[0, 0], NACK),
+            Transaction::Read(0x26, &[100, 0]),
+            Transaction::WriteError(0x25, &[1], NACK),
+            Transaction::Write(0x25, &[1]),
+        ]));
+        let mut device = device_blocking(&mut i2c).with_retries(RETRY_CONFIG);
+        assert_eq!(device.get_framerate().unwrap(), 100);
+        assert!(device.enable().is_ok());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_retries_exhausted_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::ReadError(0x26, &[0, 0], NACK),
+            Transaction::ReadError(0x26, &[0, 0], NACK),
+            Transaction::ReadError(0x26, &[0, 0], NACK),
+        ]));
+        let mut device = device_blocking(&mut i2c).with_retries(RETRY_CONFIG);
+        assert!(matches!(device.get_framerate(), Err(Error::I2c(NACK))));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_retries_invalid_data_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x23, &[7]),
+            Transaction::Read(0x23, &[1]),
+            Transaction::Read(0x23, &[7]),
+            Transaction::Read(0x23, &[7]),
+        ]));
+        let mut device = device_blocking(&mut i2c).with_retries(RETRY_CONFIG);
+        assert_eq!(device.get_ranging_mode().unwrap(), RangingMode::Trigger);
+        assert!(matches!(
+            device.get_ranging_mode(),
+            Err(Error::InvalidData(7))
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_retries_keep_ultra_low_power_detection_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::ReadError(0x28, &[0], NACK),
+            Transaction::Read(0x3C, b"LUNA"),
+        ]));
+        let mut device = device_blocking(&mut i2c).with_retries(RETRY_CONFIG);
+        assert_eq!(device.get_power_mode().unwrap(), PowerMode::UltraLow);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_retries_recover_from_transient_nack_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::ReadError(0x26, &[0, 0], NACK),
+            Transaction::ReadError(0x26, &[0, 0], NACK),
+            Transaction::Read(0x26, &[100, 0]),
+            Transaction::WriteError(0x25, &[1], NACK),
+            Transaction::Write(0x25, &[1]),
+        ]));
+        let mut device = device_async(&mut i2c).with_retries(RETRY_CONFIG);
+        assert_eq!(device.get_framerate().await.unwrap(), 100);
+        assert!(device.enable().await.is_ok());
+        i2c.done();
+    }
+
+    #[rstest]
+    #[case::zero(0, 800, 0.0, 0)]
+    #[case::half(400, 800, 0.5, 500)]
+    #[case::clamped(1000, 800, 1.0, 1000)]
+    #[case::zero_max(10, 0, 1.0, 1000)]
+    fn test_sensor_reading_distance_fraction(
+        #[case] distance: u16,
+        #[case] max: u16,
+        #[case] expected_fraction: f32,
+        #[case] expected_permille: u16,
+    ) {
+        let reading = SensorReading {
+            distance,
+            signal_strength: 100,
+            temperature: 32.5,
+            timestamp: 0,
+            error: 0,
+        };
+        assert_eq!(reading.distance_fraction(max), expected_fraction);
+        assert_eq!(reading.distance_permille(max), expected_permille);
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_distance_fraction_auto_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x30, &[0x20, 0x03]),
+            Transaction::Read(0x00, &[0xC8, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(device.distance_fraction_auto().unwrap(), 0.25);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_distance_fraction_auto_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x30, &[0x20, 0x03]),
+            Transaction::Read(0x00, &[0xC8, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(device.distance_fraction_auto().await.unwrap(), 0.25);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_describe_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x10, b"T3300245010082"),
+            Transaction::Read(0x0A, &[1, 5, 3]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(
+            device.describe().unwrap(),
+            "TF-Luna SN=T3300245010082 FW=3.5.1"
+        );
+        i2c.done();
+    }
+
+    #[cfg(all(feature = "alloc", feature = "async"))]
+    #[tokio::test]
+    async fn test_describe_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x10, b"T3300245010082"),
+            Transaction::Read(0x0A, &[1, 5, 3]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(
+            device.describe().await.unwrap(),
+            "TF-Luna SN=T3300245010082 FW=3.5.1"
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::asleep_after_communication(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x3C, b"LUNA"),
+        Transaction::ReadError(0x28, &[0], NACK),
+    ])), true, Ok(PowerMode::UltraLow))]
+    #[case::asleep_never_seen(&mut i2c_blocking(Vec::from([
+        Transaction::ReadError(0x28, &[0], NACK),
+        Transaction::Read(0x3C, b"LUNA"),
+    ])), false, Ok(PowerMode::UltraLow))]
+    #[case::wrong_address(&mut i2c_blocking(Vec::from([
+        Transaction::ReadError(0x28, &[0], NACK),
+        Transaction::ReadError(0x3C, &[0, 0, 0, 0], NACK),
+    ])), false, Err(()))]
+    fn test_get_power_mode_nack_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] seen_before: bool,
+        #[case] expected: Result<PowerMode, ()>,
+    ) {
+        let mut device = device_blocking(i2c);
+        if seen_before {
+            device.get_signature().unwrap();
+        }
+        match expected {
+            Ok(mode) => assert_eq!(device.get_power_mode().unwrap(), mode),
+            Err(()) => assert!(matches!(device.get_power_mode(), Err(Error::NotFound))),
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_power_mode_wrong_address_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::ReadError(0x28, &[0], NACK),
+            Transaction::ReadError(0x3C, &[0, 0, 0, 0], NACK),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert!(matches!(
+            device.get_power_mode().await,
+            Err(Error::NotFound)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::factor_3(3, [12, 15])]
+    #[case::factor_0(0, [10, 11])]
+    fn test_measurement_stream_decimate_blocking(
+        #[case] factor: u8,
+        #[case] expected_distances: [u16; 2],
+    ) {
+        let expectations = (10..16)
+            .take(2 * factor.max(1) as usize)
+            .map(|distance| {
+                I2cTraitTransaction::write_read(
+                    DEFAULT_SLAVE_ADDRESS,
+                    Vec::from([0x00]),
+                    Vec::from([distance, 0, 0x64, 0, 0xB2, 0x0C, distance, 0, 0, 0]),
+                )
+            })
+            .collect::<Vec<I2cTraitTransaction>>();
+        let mut i2c = I2cTraitMock::new(&expectations);
+        let mut device = device_blocking(&mut i2c);
+        let mut decimator = device.stream(1).decimate(factor);
+        assert_eq!(decimator.factor(), factor.max(1));
+        for expected in expected_distances {
+            assert_eq!(decimator.next_measurement().unwrap().distance, expected);
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_has_unsaved_changes_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x25, &[1]),
+            Transaction::Write(0x26, &[50, 0]),
+            Transaction::Write(0x30, &[0xD0, 0x07]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x23, &[1]),
+            Transaction::Write(0x21, &[2]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert!(!device.has_unsaved_changes());
+        device.enable().unwrap();
+        assert!(!device.has_unsaved_changes());
+        device.set_framerate(50).unwrap();
+        assert!(device.has_unsaved_changes());
+        device.set_maximum_distance(2000).unwrap();
+        assert!(device.has_unsaved_changes());
+        device.save_settings().unwrap();
+        assert!(!device.has_unsaved_changes());
+        device.set_ranging_mode(RangingMode::Trigger).unwrap();
+        assert!(device.has_unsaved_changes());
+        device.reboot().unwrap();
+        assert!(!device.has_unsaved_changes());
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_has_unsaved_changes_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Write(0x26, &[50, 0]),
+            Transaction::WriteError(0x20, &[1], NACK),
+            Transaction::Write(0x20, &[1]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        device.set_framerate(50).await.unwrap();
+        assert!(device.has_unsaved_changes());
+        assert!(device.save_settings().await.is_err());
+        assert!(device.has_unsaved_changes());
+        device.save_settings().await.unwrap();
+        assert!(!device.has_unsaved_changes());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::three_samples(3, 3)]
+    #[case::buffer_too_small(5, 3)]
+    fn test_trigger_burst_blocking(#[case] count: usize, #[case] expected_count: usize) {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x26, &[250, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[11, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[12, 0, 0x64, 0, 0xB2, 0x0C, 3, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        let mut buf = [SensorReading {
+            distance: 0,
+            signal_strength: 0,
+            temperature: 0.0,
+            timestamp: 0,
+            error: 0,
+        }; 3];
+        assert_eq!(
+            device.trigger_burst(count, &mut buf).unwrap(),
+            expected_count
+        );
+        assert_eq!(buf.map(|reading| reading.distance), [10, 11, 12]);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_trigger_burst_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x26, &[0, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[11, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[12, 0, 0x64, 0, 0xB2, 0x0C, 3, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        let mut buf = [SensorReading {
+            distance: 0,
+            signal_strength: 0,
+            temperature: 0.0,
+            timestamp: 0,
+            error: 0,
+        }; 3];
+        assert_eq!(device.trigger_burst(3, &mut buf).await.unwrap(), 3);
+        assert_eq!(buf.map(|reading| reading.distance), [10, 11, 12]);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::framerate_100(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x26, &[100, 0]),
+    ])), true)]
+    #[case::framerate_500(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x26, &[0xF4, 0x01]),
+    ])), false)]
+    #[case::framerate_0(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x26, &[0, 0]),
+    ])), false)]
+    fn test_is_averaged_blocking(#[case] i2c: &mut I2cTraitMock, #[case] expected: bool) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.is_averaged().unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::framerate_100(&mut i2c_async(Vec::from([
+        Transaction::Read(0x26, &[100, 0]),
+    ])), true)]
+    async fn test_is_averaged_async(#[case] i2c: &mut I2cTraitMock, #[case] expected: bool) {
+        let mut device = device_async(i2c);
+        assert_eq!(device.is_averaged().await.unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::normal(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x28, &[0]),
+    ])), 250)]
+    #[case::power_saving(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x28, &[1]),
+    ])), 10)]
+    #[case::ultra_low(&mut i2c_blocking(Vec::from([
+        Transaction::ReadError(0x28, &[0], NACK),
+        Transaction::Read(0x3C, b"LUNA"),
+    ])), 0)]
+    fn test_max_framerate_blocking(#[case] i2c: &mut I2cTraitMock, #[case] expected: u16) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.max_framerate().unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::power_saving(&mut i2c_async(Vec::from([
+        Transaction::Read(0x28, &[1]),
+    ])), 10)]
+    async fn test_max_framerate_async(#[case] i2c: &mut I2cTraitMock, #[case] expected: u16) {
+        let mut device = device_async(i2c);
+        assert_eq!(device.max_framerate().await.unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_enter_trigger_mode_and_fire_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x23, &[1]),
+            Transaction::Read(0x26, &[100, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[42, 0, 0x64, 0, 0xB2, 0x0C, 7, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        let measurement = device.enter_trigger_mode_and_fire().unwrap();
+        assert_eq!(measurement.distance, 42);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_enter_trigger_mode_and_fire_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Write(0x23, &[1]),
+            Transaction::Read(0x26, &[100, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[42, 0, 0x64, 0, 0xB2, 0x0C, 7, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        let measurement = device.enter_trigger_mode_and_fire().await.unwrap();
+        assert_eq!(measurement.distance, 42);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::present(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[10]),
+    ])), true)]
+    #[case::absent(&mut i2c_blocking(Vec::from([
+        Transaction::ReadError(0x00, &[0], NACK),
+    ])), false)]
+    #[case::bus_error(&mut i2c_blocking(Vec::from([
+        Transaction::ReadError(0x00, &[0], ErrorKind::Bus),
+    ])), false)]
+    fn test_ping_blocking(#[case] i2c: &mut I2cTraitMock, #[case] expected_ok: bool) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.ping().is_ok(), expected_ok);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_ping_distinguishes_nack_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::ReadError(0x00, &[0], NACK),
+            Transaction::ReadError(0x00, &[0], ErrorKind::Bus),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert!(matches!(device.ping(), Err(Error::NotFound)));
+        assert!(matches!(device.ping(), Err(Error::I2c(ErrorKind::Bus))));
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_ping_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[10]),
+            Transaction::ReadError(0x00, &[0], NACK),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert!(device.ping().await.is_ok());
+        assert!(matches!(device.ping().await, Err(Error::NotFound)));
+        i2c.done();
+    }
+
+    #[rstest]
+    #[case::zero(0, 0)]
+    #[case::one(1, 256)]
+    #[case::max(u16::MAX, 0xFF_FF00)]
+    fn test_sensor_reading_distance_q8(#[case] distance: u16, #[case] expected: u32) {
+        let reading = SensorReading {
+            distance,
+            signal_strength: 100,
+            temperature: 32.5,
+            timestamp: 0,
+            error: 0,
+        };
+        assert_eq!(reading.distance_q8(), expected);
+    }
+
+    #[rstest]
+    #[case::empty(&[], None)]
+    #[case::single(&[100], Some(100 << 8))]
+    #[case::half(&[100, 101], Some((100 << 8) + 128))]
+    #[case::third(&[100, 100, 101], Some((100 << 8) + 85))]
+    fn test_sensor_reading_average_distance_q8(
+        #[case] distances: &[u16],
+        #[case] expected: Option<u32>,
+    ) {
+        let readings: Vec<SensorReading> = distances
+            .iter()
+            .map(|&distance| SensorReading {
+                distance,
+                signal_strength: 100,
+                temperature: 32.5,
+                timestamp: 0,
+                error: 0,
+            })
+            .collect();
+        assert_eq!(SensorReading::average_distance_q8(&readings), expected);
+    }
+
+    #[rstest]
+    #[case::older_major(FirmwareVersion { major: 2, minor: 9, revision: 9 }, false)]
+    #[case::older_revision(FirmwareVersion { major: 3, minor: 5, revision: 0 }, false)]
+    #[case::same(FirmwareVersion { major: 3, minor: 5, revision: 1 }, true)]
+    #[case::newer_minor(FirmwareVersion { major: 3, minor: 6, revision: 0 }, true)]
+    fn test_firmware_version_ordering(#[case] version: FirmwareVersion, #[case] at_least: bool) {
+        let reference = FirmwareVersion {
+            major: 3,
+            minor: 5,
+            revision: 1,
+        };
+        assert_eq!(version >= reference, at_least);
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::supported(FirmwareVersion { major: 3, minor: 5, revision: 0 }, true)]
+    #[case::unsupported(FirmwareVersion { major: 3, minor: 6, revision: 0 }, false)]
+    fn test_require_firmware_blocking(#[case] min: FirmwareVersion, #[case] supported: bool) {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Read(0x0A, &[1, 5, 3])]));
+        let mut device = device_blocking(&mut i2c);
+        let result = device.require_firmware(min);
+        if supported {
+            assert!(result.is_ok(), "{:?}", result);
+        } else {
+            assert!(
+                matches!(
+                    result,
+                    Err(Error::UnsupportedFirmware { found, required })
+                        if found == FirmwareVersion { major: 3, minor: 5, revision: 1 } && required == min
+                ),
+                "{:?}",
+                result
+            );
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_require_firmware_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Read(0x0A, &[1, 5, 3])]));
+        let mut device = device_async(&mut i2c);
+        let min = FirmwareVersion {
+            major: 3,
+            minor: 6,
+            revision: 0,
+        };
+        assert!(matches!(
+            device.require_firmware(min).await,
+            Err(Error::UnsupportedFirmware { .. })
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_retries_arbitration_loss_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::ReadError(0x00, &[0; 10], ErrorKind::ArbitrationLoss),
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+            Transaction::WriteError(0x24, &[1], ErrorKind::ArbitrationLoss),
+            Transaction::WriteError(0x24, &[1], ErrorKind::ArbitrationLoss),
+        ]));
+        let mut device = device_blocking(&mut i2c).with_retries(RETRY_CONFIG);
+        assert_eq!(device.get_measurement().unwrap().distance, 10);
+        // Only one arbitration loss retry is configured
+        assert!(matches!(
+            device.trigger_measurement(),
+            Err(Error::I2c(ErrorKind::ArbitrationLoss))
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_retries_arbitration_loss_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::ReadError(0x00, &[0; 10], ErrorKind::ArbitrationLoss),
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c).with_retries(RETRY_CONFIG);
+        assert_eq!(device.get_measurement().await.unwrap().distance, 10);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::asleep(&mut i2c_blocking(Vec::from([
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::Read(0x23, &[1]),
+        Transaction::Write(0x24, &[1]),
+        Transaction::Read(0x00, &[42, 0, 0x64, 0, 0xB2, 0x0C, 7, 0, 0, 0]),
+    ])))]
+    #[case::awake(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[40]),
+        Transaction::Read(0x23, &[1]),
+        Transaction::Write(0x24, &[1]),
+        Transaction::Read(0x00, &[42, 0, 0x64, 0, 0xB2, 0x0C, 7, 0, 0, 0]),
+    ])))]
+    #[case::continuous(&mut i2c_blocking(Vec::from([
+        Transaction::ReadError(0x00, &[0], NACK),
+        Transaction::Read(0x23, &[0]),
+        Transaction::Write(0x23, &[1]),
+        Transaction::Write(0x24, &[1]),
+        Transaction::Read(0x00, &[42, 0, 0x64, 0, 0xB2, 0x0C, 7, 0, 0, 0]),
+    ])))]
+    fn test_duty_cycle_read_blocking(#[case] i2c: &mut I2cTraitMock) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.duty_cycle_read().unwrap().distance, 42);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_duty_cycle_read_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::ReadError(0x00, &[0], NACK),
+            Transaction::Read(0x23, &[1]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[42, 0, 0x64, 0, 0xB2, 0x0C, 7, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(device.duty_cycle_read().await.unwrap().distance, 42);
+        i2c.done();
+    }
+
+    #[test]
+    fn test_measurement_view() {
+        let buffer = [10, 0, 0x64, 0, 0x0C, 0xFE, 0x34, 0x12, 0, 0];
+        let view = MeasurementView::new(&buffer);
+        assert_eq!(view.temperature(), -5.0);
+        assert_eq!(view.timestamp(), 0x1234);
+        assert_eq!(
+            view.to_reading(),
+            SensorReading {
+                distance: 10,
+                signal_strength: 100,
+                temperature: -5.0,
+                timestamp: 0x1234,
+                error: 0,
+            }
+        );
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_read_measurement_view_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Read(
+            0x00,
+            &[0x2C, 0x01, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0],
+        )]));
+        let mut device = device_blocking(&mut i2c);
+        let mut buffer = [0; 10];
+        let view = device.read_measurement_view(&mut buffer).unwrap();
+        assert_eq!(view.distance(), 300);
+        assert_eq!(view.signal_strength(), 100);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_read_measurement_view_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Read(
+            0x00,
+            &[0x2C, 0x01, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0],
+        )]));
+        let mut device = device_async(&mut i2c);
+        let mut buffer = [0; 10];
+        let view = device.read_measurement_view(&mut buffer).await.unwrap();
+        assert_eq!(view.distance(), 300);
+        i2c.done();
+    }
+
+    #[rstest]
+    #[case::after_sync(1000, 1250, 250)]
+    #[case::wrapped(0xFFF0, 0x0010, 0x20)]
+    fn test_timestamp_base(#[case] sync: u16, #[case] timestamp: u16, #[case] expected: u16) {
+        let base = TimestampBase::new(sync);
+        assert_eq!(base.ticks_since_sync(timestamp), expected);
+        let reading = SensorReading {
+            distance: 10,
+            signal_strength: 100,
+            temperature: 32.5,
+            timestamp,
+            error: 0,
+        };
+        assert_eq!(base.align(reading).timestamp, expected);
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_sync_timestamp_base_two_devices_blocking() {
+        // Two devices sharing the same bus, powered on at different times
+        let mut i2c = I2cTraitMock::new(&[
+            I2cTraitTransaction::write_read(0x10, Vec::from([0x06]), Vec::from([0xE8, 0x03])),
+            I2cTraitTransaction::write_read(0x11, Vec::from([0x06]), Vec::from([0x10, 0x27])),
+        ]);
+        let mut first = TFLunaBlocking::new(i2c.clone(), Address::from(0x10), Delay {}).unwrap();
+        let mut second = TFLunaBlocking::new(i2c.clone(), Address::from(0x11), Delay {}).unwrap();
+        let first_base = first.sync_timestamp_base().unwrap();
+        let second_base = second.sync_timestamp_base().unwrap();
+        assert_eq!(first_base.sync_timestamp(), 1000);
+        assert_eq!(second_base.sync_timestamp(), 10000);
+        // Measurements taken at the same time have the same aligned timestamp
+        assert_eq!(
+            first_base.ticks_since_sync(1100),
+            second_base.ticks_since_sync(10100)
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_sync_timestamp_base_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Read(0x06, &[0xE8, 0x03])]));
+        let mut device = device_async(&mut i2c);
+        let base = device.sync_timestamp_base().await.unwrap();
+        assert_eq!(base.sync_timestamp(), 1000);
+        i2c.done();
+    }
+
+    #[rstest]
+    #[case::room(32.5, false)]
+    #[case::at_limit(60.0, false)]
+    #[case::above_limit(60.01, true)]
+    #[case::below_zero(-5.0, false)]
+    fn test_sensor_reading_is_over_temperature(#[case] temperature: f32, #[case] expected: bool) {
+        let reading = SensorReading {
+            distance: 10,
+            signal_strength: 100,
+            temperature,
+            timestamp: 0,
+            error: 0,
+        };
+        assert_eq!(reading.is_over_temperature(), expected);
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_robust_distance_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x26, &[250, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::Write(0x24, &[1]),
+            // Spike
+            Transaction::Read(0x00, &[0xD0, 0x07, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+            Transaction::Write(0x24, &[1]),
+            // Invalid, skipped
+            Transaction::Read(0x00, &[5, 0, 0x0A, 0, 0xB2, 0x0C, 3, 0, 0, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[102, 0, 0x64, 0, 0xB2, 0x0C, 4, 0, 0, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[101, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(device.get_robust_distance(5).unwrap(), 101);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::no_samples(0)]
+    #[case::too_many_samples(17)]
+    fn test_get_robust_distance_invalid_samples_blocking(#[case] samples: u8) {
+        let mut i2c = i2c_blocking(Vec::new());
+        let mut device = device_blocking(&mut i2c);
+        assert!(matches!(
+            device.get_robust_distance(samples),
+            Err(Error::InvalidParameter)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_robust_distance_no_valid_sample_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x26, &[250, 0]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[5, 0, 0x0A, 0, 0xB2, 0x0C, 3, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert!(matches!(
+            device.get_robust_distance(1).await,
+            Err(Error::NoValidSamples)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::minimum_range(20, 6)]
+    #[case::fixed_accuracy(299, 6)]
+    #[case::fixed_accuracy_upper_bound(300, 6)]
+    #[case::relative_accuracy(450, 9)]
+    #[case::relative_accuracy_rounded_up(501, 11)]
+    #[case::maximum_range(800, 16)]
+    fn test_distance_resolution_cm(#[case] distance: u16, #[case] expected: u16) {
+        let mut i2c = i2c_blocking(Vec::new());
+        let device = device_blocking(&mut i2c);
+        assert_eq!(device.distance_resolution_cm(distance), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_take_new_data_flag_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x06, &[0x10, 0x00]),
+            Transaction::Read(0x06, &[0x10, 0x00]),
+            Transaction::Read(0x06, &[0x12, 0x00]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert!(device.take_new_data_flag().unwrap());
+        assert!(!device.take_new_data_flag().unwrap());
+        assert!(device.take_new_data_flag().unwrap());
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_take_new_data_flag_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x06, &[0x10, 0x00]),
+            Transaction::Read(0x06, &[0x10, 0x00]),
+            Transaction::Read(0x06, &[0x12, 0x00]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert!(device.take_new_data_flag().await.unwrap());
+        assert!(!device.take_new_data_flag().await.unwrap());
+        assert!(device.take_new_data_flag().await.unwrap());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::indoor_short_range(Preset::IndoorShortRange, [0], [100, 0], [0x2C, 0x01])]
+    #[case::outdoor_long_range(Preset::OutdoorLongRange, [0], [50, 0], [0x20, 0x03])]
+    #[case::low_power(Preset::LowPower, [1], [5, 0], [0x20, 0x03])]
+    fn test_apply_preset_blocking(
+        #[case] preset: Preset,
+        #[case] power_saving: [u8; 1],
+        #[case] framerate: [u8; 2],
+        #[case] maximum_distance: [u8; 2],
+    ) {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x00, &[0]),
+            Transaction::Write(0x1F, &[0]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::Write(0x28, &power_saving),
+            Transaction::Write(0x23, &[0]),
+            Transaction::Write(0x26, &framerate),
+            Transaction::Write(0x2A, &[100, 0]),
+            Transaction::Write(0x2C, &[0, 0]),
+            Transaction::Write(0x2E, &[20, 0]),
+            Transaction::Write(0x30, &maximum_distance),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.apply_preset(preset).unwrap();
+        assert!(device.has_unsaved_changes());
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_apply_preset_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[0]),
+            Transaction::Write(0x1F, &[0]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::Write(0x28, &[1]),
+            Transaction::Write(0x23, &[0]),
+            Transaction::Write(0x26, &[5, 0]),
+            Transaction::Write(0x2A, &[100, 0]),
+            Transaction::Write(0x2C, &[0, 0]),
+            Transaction::Write(0x2E, &[20, 0]),
+            Transaction::Write(0x30, &[0x20, 0x03]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        device.apply_preset(Preset::LowPower).await.unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::wrong_signature(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x3C, b"TFMP"),
+    ])))]
+    #[case::nack(&mut i2c_blocking(Vec::from([
+        Transaction::ReadError(0x3C, &[0, 0, 0, 0], NACK),
+    ])))]
+    fn test_new_probed_not_found_blocking(#[case] i2c: &mut I2cTraitMock) {
+        assert!(matches!(
+            TFLunaBlocking::new_probed(&mut *i2c, Address::default(), Delay {}),
+            Err(Error::NotFound)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_new_probed_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Read(0x3C, b"LUNA")]));
+        assert!(TFLunaBlocking::new_probed(&mut i2c, Address::default(), Delay {}).is_ok());
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_new_probed_wrong_signature_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Read(0x3C, b"TFMP")]));
+        assert!(matches!(
+            TFLunaAsync::new_probed(&mut i2c, Address::default(), Delay {}).await,
+            Err(Error::NotFound)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_set_framerate_zero_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x26, &[0, 0]),
+            Transaction::Read(0x26, &[0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_framerate(0).unwrap();
+        assert!(!device.is_averaged().unwrap());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_wait_for_stable_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x00, &[150, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::Read(0x00, &[120, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+            // Same measurement as the previous read, ignored
+            Transaction::Read(0x00, &[120, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+            Transaction::Read(0x00, &[102, 0, 0x64, 0, 0xB2, 0x0C, 3, 0, 0, 0]),
+            // Not reliable, empties the window
+            Transaction::Read(0x00, &[101, 0, 0x0A, 0, 0xB2, 0x0C, 4, 0, 0, 0]),
+            Transaction::Read(0x00, &[104, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+            Transaction::Read(0x00, &[102, 0, 0x64, 0, 0xB2, 0x0C, 6, 0, 0, 0]),
+            Transaction::Read(0x00, &[103, 0, 0x64, 0, 0xB2, 0x0C, 7, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(device.wait_for_stable(2, 3, 1000).unwrap(), 103);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_wait_for_stable_timeout_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[150, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::Read(0x00, &[120, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+            Transaction::Read(0x00, &[102, 0, 0x64, 0, 0xB2, 0x0C, 3, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert!(matches!(
+            device.wait_for_stable(2, 2, 20).await,
+            Err(Error::Timeout)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_filtered_distance_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x30, &[0x2C, 0x01]),
+            // Returned as read by the device, even beyond the maximum distance
+            Transaction::Read(0x00, &[0x90, 0x01]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_maximum_distance(300).unwrap();
+        assert_eq!(device.get_filtered_distance().unwrap(), 400);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_filtered_distance_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Read(0x00, &[0x2C, 0x01])]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(device.get_filtered_distance().await.unwrap(), 300);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::repeated_start(ReadMode::RepeatedStart, &[
+        I2cTraitTransaction::write_read(DEFAULT_SLAVE_ADDRESS, Vec::from([0x26]), Vec::from([100, 0])),
+    ])]
+    #[case::stop_start(ReadMode::StopStart, &[
+        I2cTraitTransaction::write(DEFAULT_SLAVE_ADDRESS, Vec::from([0x26])),
+        I2cTraitTransaction::read(DEFAULT_SLAVE_ADDRESS, Vec::from([100, 0])),
+    ])]
+    fn test_read_mode_blocking(
+        #[case] read_mode: ReadMode,
+        #[case] expectations: &[I2cTraitTransaction],
+    ) {
+        let mut i2c = I2cTraitMock::new(expectations);
+        let mut device = device_blocking(&mut i2c).with_read_mode(read_mode);
+        assert_eq!(device.get_framerate().unwrap(), 100);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_read_mode_stop_start_async() {
+        let mut i2c = I2cTraitMock::new(&[
+            I2cTraitTransaction::write(DEFAULT_SLAVE_ADDRESS, Vec::from([0x26])),
+            I2cTraitTransaction::read(DEFAULT_SLAVE_ADDRESS, Vec::from([100, 0])),
+        ]);
+        let mut device = device_async(&mut i2c).with_read_mode(ReadMode::StopStart);
+        assert_eq!(device.get_framerate().await.unwrap(), 100);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::matching(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x22, &[0x10]),
+    ])), true)]
+    #[case::mismatching(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x22, &[0x20]),
+    ])), false)]
+    fn test_verify_address_blocking(#[case] i2c: &mut I2cTraitMock, #[case] expected: bool) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.verify_address().unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[rstest]
+    #[tokio::test]
+    #[case::matching(&mut i2c_async(Vec::from([
+        Transaction::Read(0x22, &[0x10]),
+    ])), true)]
+    #[case::mismatching(&mut i2c_async(Vec::from([
+        Transaction::Read(0x22, &[0x20]),
+    ])), false)]
+    async fn test_verify_address_async(#[case] i2c: &mut I2cTraitMock, #[case] expected: bool) {
+        let mut device = device_async(i2c);
+        assert_eq!(device.verify_address().await.unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_measurement_stream_statistics_blocking() {
+        let expectations = [2, 4, 4, 4, 5, 5, 7, 9, 9]
+            .into_iter()
+            .map(|distance| {
+                I2cTraitTransaction::write_read(
+                    DEFAULT_SLAVE_ADDRESS,
+                    Vec::from([0x00]),
+                    Vec::from([distance, 0, 0x64, 0, 0xB2, 0x0C, distance, 0, 0, 0]),
+                )
+            })
+            .collect::<Vec<I2cTraitTransaction>>();
+        let mut i2c = I2cTraitMock::new(&expectations);
+        let mut device = device_blocking(&mut i2c);
+        let mut statistics = device.stream(1).statistics::<8>();
+        assert_eq!(statistics.stats(), None);
+        for _ in 0..8 {
+            statistics.next_measurement().unwrap();
+        }
+        let stats = statistics.stats().unwrap();
+        assert_eq!((stats.count, stats.minimum, stats.maximum), (8, 2, 9));
+        assert_eq!(stats.mean(), 5.0);
+        assert_eq!(stats.mean_q8(), 5 << 8);
+        assert_eq!(stats.std_dev(), 2.0);
+        assert_eq!(stats.std_dev_q8(), 2 << 8);
+        // The first distance leaves the window
+        statistics.next_measurement().unwrap();
+        let stats = statistics.stats().unwrap();
+        assert_eq!((stats.count, stats.minimum, stats.maximum), (8, 4, 9));
+        assert_eq!(stats.mean(), 5.875);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_measurement_stream_statistics_skips_unreliable_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::Read(0x00, &[50, 0, 0x0A, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+            Transaction::Read(0x00, &[12, 0, 0x64, 0, 0xB2, 0x0C, 3, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        let mut statistics = device.stream(1).statistics::<4>();
+        for _ in 0..3 {
+            statistics.next_measurement().await.unwrap();
+        }
+        let stats = statistics.stats().unwrap();
+        assert_eq!((stats.count, stats.minimum, stats.maximum), (2, 10, 12));
+        assert_eq!(stats.mean(), 11.0);
+        assert_eq!(stats.std_dev(), 1.0);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_measurement_fresh_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+            // Same frame, wait for one frame period at 100Hz
+            Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+            Transaction::Read(0x28, &[0]),
+            Transaction::Read(0x26, &[100, 0]),
+            Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+            Transaction::Read(0x00, &[101, 0, 0x64, 0, 0xB2, 0x0C, 6, 0, 0, 0]),
+            // New frame, returned right away
+            Transaction::Read(0x00, &[102, 0, 0x64, 0, 0xB2, 0x0C, 7, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(device.get_measurement_fresh().unwrap().timestamp, 5);
+        assert_eq!(device.get_measurement_fresh().unwrap().timestamp, 6);
+        assert_eq!(device.get_measurement_fresh().unwrap().timestamp, 7);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_measurement_fresh_timeout_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+            Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+            Transaction::Read(0x28, &[1]),
+            Transaction::Read(0x26, &[100, 0]),
+            Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+            Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+            Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        device.get_measurement_fresh().await.unwrap();
+        assert!(matches!(
+            device.get_measurement_fresh().await,
+            Err(Error::Timeout)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_release_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x25, &[1]),
+            Transaction::Read(0x26, &[100, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.enable().unwrap();
+        let (i2c_released, delay) = device.release();
+        // The released bus and delay can be used to create a new instance
+        let mut device = TFLunaBlocking::new(i2c_released, Address::default(), delay).unwrap();
+        assert_eq!(device.get_framerate().unwrap(), 100);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_release_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Write(0x25, &[1])]));
+        let mut device = device_async(&mut i2c);
+        device.enable().await.unwrap();
+        let (i2c_released, _delay) = device.release();
+        i2c_released.done();
+    }
+
+    #[rstest]
+    #[case::valid(0, 100, false, false, true)]
+    #[case::weak_signal(0, 99, false, true, false)]
+    #[case::saturated(0, 65535, true, false, false)]
+    #[case::device_error(1, 500, false, false, false)]
+    fn test_sensor_reading_validity(
+        #[case] error: u16,
+        #[case] signal_strength: u16,
+        #[case] saturated: bool,
+        #[case] below_threshold: bool,
+        #[case] valid: bool,
+    ) {
+        let reading = SensorReading {
+            distance: 100,
+            signal_strength,
+            temperature: 25.0,
+            timestamp: 0,
+            error,
+        };
+        assert_eq!(reading.is_saturated(), saturated);
+        assert_eq!(reading.is_below_threshold(100), below_threshold);
+        assert_eq!(reading.is_valid(), valid);
+    }
+
+    #[rstest]
+    #[case::zero(0, 0, 0.0)]
+    #[case::one_meter(100, 1000, 1.0)]
+    #[case::maximum_range(9000, 90000, 90.0)]
+    fn test_sensor_reading_distance_units(
+        #[case] distance: u16,
+        #[case] expected_mm: u32,
+        #[case] expected_m: f32,
+    ) {
+        let reading = SensorReading {
+            distance,
+            signal_strength: 100,
+            temperature: 32.5,
+            timestamp: 0,
+            error: 0,
+        };
+        assert_eq!(reading.distance_mm(), expected_mm);
+        assert_eq!(reading.distance_m(), expected_m);
+    }
+
+    #[test]
+    fn test_sensor_reading_temperature_units() {
+        let reading = SensorReading {
+            distance: 100,
+            signal_strength: 100,
+            temperature: 32.5,
+            timestamp: 0,
+            error: 0,
+        };
+        assert_eq!(reading.temperature_fahrenheit(), 90.5);
+        assert!((reading.temperature_kelvin() - 305.65).abs() < 1e-3);
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_builder_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x26, &[50, 0]),
+            Transaction::Write(0x23, &[0]),
+            Transaction::Write(0x2A, &[100, 0]),
+            Transaction::Write(0x2E, &[20, 0]),
+            Transaction::Write(0x30, &[0x2C, 0x01]),
+            Transaction::Write(0x20, &[1]),
+        ]));
+        let device = TFLunaBlocking::builder()
+            .framerate(50)
+            .unwrap()
+            .ranging_mode(RangingMode::Continuous)
+            .signal_strength_threshold(100)
+            .minimum_distance(20)
+            .unwrap()
+            .maximum_distance(300)
+            .unwrap()
+            .save_settings(true)
+            .build(&mut i2c, Address::default(), Delay {})
+            .unwrap();
+        assert!(!device.has_unsaved_changes());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_builder_partial_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Write(0x30, &[0x20, 0x03])]));
+        let device = TFLunaBlocking::builder()
+            .maximum_distance(800)
+            .unwrap()
+            .build(&mut i2c, Address::default(), Delay {})
+            .unwrap();
+        assert!(device.has_unsaved_changes());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_builder_stops_on_error_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::WriteError(0x26, &[50, 0], NACK)]));
+        let result = TFLunaBlocking::builder()
+            .framerate(50)
+            .unwrap()
+            .ranging_mode(RangingMode::Trigger)
+            .build(&mut i2c, Address::default(), Delay {});
+        assert!(matches!(result, Err(Error::I2c(_))));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::framerate(TFLunaBlocking::<&mut I2cTraitMock, Delay>::builder().framerate(3).map(|_| ()))]
+    #[case::minimum_above_maximum(
+        TFLunaBlocking::<&mut I2cTraitMock, Delay>::builder()
+            .maximum_distance(100)
+            .unwrap()
+            .minimum_distance(200)
+            .map(|_| ())
+    )]
+    #[case::maximum_below_minimum(
+        TFLunaBlocking::<&mut I2cTraitMock, Delay>::builder()
+            .minimum_distance(200)
+            .unwrap()
+            .maximum_distance(100)
+            .map(|_| ())
+    )]
+    fn test_builder_invalid_parameter(#[case] result: Result<(), Error<ErrorKind>>) {
+        assert!(matches!(result, Err(Error::InvalidParameter)));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_builder_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Write(0x26, &[50, 0]),
+            Transaction::Write(0x20, &[1]),
+        ]));
+        let builder: TFLunaBuilderAsync<_, _> = TFLunaAsync::builder();
+        builder
+            .framerate(50)
+            .unwrap()
+            .save_settings(true)
+            .build(&mut i2c, Address::default(), Delay {})
+            .await
+            .unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_config_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x26, &[5, 0]),
+            Transaction::Read(0x23, &[1]),
+            Transaction::Read(0x2A, &[80, 0]),
+            Transaction::Read(0x2C, &[0xE8, 0x03]),
+            Transaction::Read(0x2E, &[20, 0]),
+            Transaction::Read(0x30, &[0x2C, 0x01]),
+            Transaction::Read(0x28, &[1]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(
+            device.get_config().unwrap(),
+            Config {
+                framerate: 5,
+                ranging_mode: RangingMode::Trigger,
+                power_mode: PowerMode::PowerSaving,
+                signal_strength_threshold: 80,
+                dummy_distance: 1000,
+                minimum_distance: 20,
+                maximum_distance: 300,
+            }
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_config_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x26, &[100, 0]),
+            Transaction::Read(0x23, &[0]),
+            Transaction::Read(0x2A, &[100, 0]),
+            Transaction::Read(0x2C, &[0, 0]),
+            Transaction::Read(0x2E, &[0, 0]),
+            Transaction::Read(0x30, &[0x20, 0x03]),
+            Transaction::Read(0x28, &[0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(device.get_config().await.unwrap(), PROVISIONED_CONFIG);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_apply_config_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x00, &[0]),
+            Transaction::Write(0x1F, &[0]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::Write(0x28, &[0]),
+            Transaction::Write(0x23, &[0]),
+            Transaction::Write(0x26, &[100, 0]),
+            Transaction::Write(0x2A, &[100, 0]),
+            Transaction::Write(0x2C, &[0, 0]),
+            Transaction::Write(0x2E, &[0, 0]),
+            Transaction::Write(0x30, &[0x20, 0x03]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.apply_config(&PROVISIONED_CONFIG).unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_apply_config_ultra_low_power_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x23, &[1]),
+            Transaction::Write(0x26, &[0, 0]),
+            Transaction::Write(0x2A, &[100, 0]),
+            Transaction::Write(0x2C, &[0, 0]),
+            Transaction::Write(0x2E, &[0, 0]),
+            Transaction::Write(0x30, &[0x20, 0x03]),
+            Transaction::Write(0x1F, &[1]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        let config = Config {
+            power_mode: PowerMode::UltraLow,
+            ranging_mode: RangingMode::Trigger,
+            framerate: 0,
+            ..PROVISIONED_CONFIG
+        };
+        device.apply_config(&config).unwrap();
+        assert!(matches!(
+            device.set_framerate(50),
+            Err(Error::UltraLowPowerActive)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_apply_config_stops_on_error_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x00, &[0]),
+            Transaction::Write(0x1F, &[0]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::Write(0x28, &[0]),
+            Transaction::Write(0x23, &[0]),
+            Transaction::WriteError(0x26, &[100, 0], NACK),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert!(matches!(
+            device.apply_config(&PROVISIONED_CONFIG),
+            Err(Error::I2c(_))
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_apply_config_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[0]),
+            Transaction::Write(0x1F, &[0]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::Write(0x28, &[0]),
+            Transaction::Write(0x23, &[0]),
+            Transaction::Write(0x26, &[100, 0]),
+            Transaction::Write(0x2A, &[100, 0]),
+            Transaction::Write(0x2C, &[0, 0]),
+            Transaction::Write(0x2E, &[0, 0]),
+            Transaction::Write(0x30, &[0x20, 0x03]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        device.apply_config(&PROVISIONED_CONFIG).await.unwrap();
+        i2c.done();
+    }
+
+    /// Expectations for a full bus scan, with the given devices answering and NACKs elsewhere.
+    fn scan_expectations(devices: &[(u8, &[u8; 4])]) -> Vec<I2cTraitTransaction> {
+        (0x08..=0x77)
+            .map(
+                |address| match devices.iter().find(|(device, _)| *device == address) {
+                    Some((_, signature)) => I2cTraitTransaction::write_read(
+                        address,
+                        Vec::from([0x3C]),
+                        Vec::from(*signature),
+                    ),
+                    None => I2cTraitTransaction::write_read(
+                        address,
+                        Vec::from([0x3C]),
+                        Vec::from([0; 4]),
+                    )
+                    .with_error(NACK),
+                },
+            )
+            .collect()
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_scan_addresses_blocking() {
+        let mut i2c = I2cTraitMock::new(&scan_expectations(&[
+            (0x10, b"LUNA"),
+            (0x29, b"\x00\x00\x00\x00"),
+            (0x42, b"LUNA"),
+        ]));
+        let found = embedded_tfluna::i2c::blocking::scan_addresses(&mut i2c).unwrap();
+        assert_ne!(found, AddressSet::default());
+        assert_eq!(found.len(), 2);
+        assert!(found.contains(Address::from(0x10)));
+        assert!(!found.contains(Address::from(0x29)));
+        assert_eq!(
+            found.iter().collect::<Vec<_>>(),
+            [Address::from(0x10), Address::from(0x42)]
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_scan_addresses_bus_error_blocking() {
+        let mut i2c = I2cTraitMock::new(&[I2cTraitTransaction::write_read(
+            0x08,
+            Vec::from([0x3C]),
+            Vec::from([0; 4]),
+        )
+        .with_error(ErrorKind::Bus)]);
+        assert!(matches!(
+            embedded_tfluna::i2c::blocking::scan_addresses(&mut i2c),
+            Err(Error::I2c(ErrorKind::Bus))
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_scan_addresses_async() {
+        let mut i2c = I2cTraitMock::new(&scan_expectations(&[]));
+        let found = embedded_tfluna::i2c::asynchronous::scan_addresses(&mut i2c)
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+        i2c.done();
+    }
+
+    #[rstest]
+    #[case::luna(*b"LUNA", true)]
+    #[case::other(*b"TFMP", false)]
+    #[case::zeros([0; 4], false)]
+    fn test_signature_is_valid(#[case] bytes: [u8; 4], #[case] expected: bool) {
+        assert_eq!(Signature(bytes).is_valid(), expected);
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::luna(b"LUNA", None)]
+    #[case::other(b"LUNB", Some(b'B'))]
+    fn test_verify_signature_blocking(#[case] signature: &[u8], #[case] invalid: Option<u8>) {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Read(0x3C, signature)]));
+        let mut device = device_blocking(&mut i2c);
+        match invalid {
+            None => device.verify_signature().unwrap(),
+            Some(byte) => assert!(matches!(
+                device.verify_signature(),
+                Err(Error::InvalidData(b)) if b == byte
+            )),
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_verify_signature_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Read(0x3C, b"TFMP")]));
+        let mut device = device_async(&mut i2c);
+        assert!(matches!(
+            device.verify_signature().await,
+            Err(Error::InvalidData(b'T'))
+        ));
+        i2c.done();
+    }
+
+    #[test]
+    fn test_serial_number_as_str() {
+        let serial_number = SerialNumber(*b"T3300245010082");
+        assert_eq!(serial_number.as_str(), Ok("T3300245010082"));
+        assert_eq!(std::format!("{}", serial_number), "T3300245010082");
+    }
+
+    #[test]
+    fn test_serial_number_invalid_ascii() {
+        let mut bytes = *b"T3300245010082";
+        bytes[0] = 0xFF;
+        bytes[13] = 0x00;
+        let serial_number = SerialNumber(bytes);
+        assert!(serial_number.as_str().is_err());
+        assert_eq!(std::format!("{}", serial_number), "?330024501008?");
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_measure_if_ready_blocking() {
+        let mut i2c = I2cTraitMock::new(&read_expectations(
+            0x00,
+            &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0],
+        ));
+        let mut pin = PinMock::new(&[
+            PinTransaction::get(PinState::Low),
+            PinTransaction::get(PinState::High),
+        ]);
+        let mut device = device_blocking(&mut i2c).with_data_ready(&mut pin);
+        assert_eq!(device.measure_if_ready().unwrap(), None);
+        assert_eq!(device.measure_if_ready().unwrap().unwrap().distance, 10);
+        pin.done();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_measure_if_ready_pin_error_blocking() {
+        let mut i2c = i2c_blocking(Vec::new());
+        let mut pin = PinMock::new(&[PinTransaction::get(PinState::High)
+            .with_error(PinMockError::Io(std::io::ErrorKind::Other))]);
+        let mut device = device_blocking(&mut i2c).with_data_ready(&mut pin);
+        assert!(matches!(device.measure_if_ready(), Err(Error::Pin)));
+        pin.done();
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_measure_if_ready_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Read(
+            0x00,
+            &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0],
+        )]));
+        let mut pin = PinMock::new(&[PinTransaction::get(PinState::High)]);
+        let mut device = device_async(&mut i2c).with_data_ready(&mut pin);
+        assert_eq!(
+            device.measure_if_ready().await.unwrap().unwrap().distance,
+            10
+        );
+        pin.done();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_measurement_stream_iterator_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::ReadError(0x00, &[0; 10], ErrorKind::Bus),
+            Transaction::Read(0x00, &[11, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        let results = device.stream(0).take(3).collect::<Vec<_>>();
+        assert_eq!(results[0].as_ref().unwrap().distance, 10);
+        assert!(matches!(results[1], Err(Error::I2c(ErrorKind::Bus))));
+        assert_eq!(results[2].as_ref().unwrap().distance, 11);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_write_settings_in_ultra_low_power_mode_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x1F, &[1]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+            // Wake-up read, not acknowledged while asleep
+            Transaction::ReadError(0x00, &[0], NACK),
+            Transaction::Write(0x26, &[50, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_power_mode(PowerMode::UltraLow).unwrap();
+        assert!(matches!(
+            device.set_framerate(50),
+            Err(Error::UltraLowPowerActive)
+        ));
+        assert!(matches!(
+            device.set_ranging_mode(RangingMode::Trigger),
+            Err(Error::UltraLowPowerActive)
+        ));
+        device.wake_from_ultra_low_power().unwrap();
+        device.set_framerate(50).unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_assume_awake_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x1F, &[1]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::Write(0x2E, &[20, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_power_mode(PowerMode::UltraLow).unwrap();
+        device.assume_awake();
+        device.set_minimum_distance(20).unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_write_settings_in_ultra_low_power_mode_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Write(0x1F, &[1]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        device.set_power_mode(PowerMode::UltraLow).await.unwrap();
+        assert!(matches!(
+            device.set_maximum_distance(300).await,
+            Err(Error::UltraLowPowerActive)
+        ));
+        i2c.done();
+    }
+
+    #[rstest]
+    #[case::i2c(Error::I2c(ErrorKind::Bus), "I2C error: ")]
+    #[case::invalid_data(Error::InvalidData(0x0F), "invalid data returned by device: 0x0f")]
+    #[case::invalid_parameter(Error::InvalidParameter, "invalid parameter")]
+    #[case::unsupported_firmware(
+        Error::UnsupportedFirmware {
+            found: FirmwareVersion { major: 3, minor: 5, revision: 1 },
+            required: FirmwareVersion { major: 3, minor: 6, revision: 0 },
+        },
+        "unsupported firmware version 3.5.1, 3.6.0 or later is required"
+    )]
+    #[case::no_valid_samples(Error::NoValidSamples, "no reliable measurement among the samples")]
+    #[case::pin(Error::Pin, "data-ready pin could not be read")]
+    #[case::other(Error::Other, "other error")]
+    fn test_error_display(#[case] error: Error<ErrorKind>, #[case] expected: &str) {
+        let description = std::format!("{}", error);
+        assert!(
+            description.starts_with(expected),
+            "{description:?} doesn't start with {expected:?}"
+        );
+        let _: &dyn core::error::Error = &error;
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_raw_register_access_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x3F, &[0x5A]),
+            Transaction::Write(0x3F, &[0xA5]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(device.read_register(0x3F).unwrap(), 0x5A);
+        device.write_register(0x3F, 0xA5).unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_raw_register_access_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x3F, &[0x5A]),
+            Transaction::WriteError(0x3F, &[0xA5], ErrorKind::Bus),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(device.read_register(0x3F).await.unwrap(), 0x5A);
+        assert!(matches!(
+            device.write_register(0x3F, 0xA5).await,
+            Err(Error::I2c(ErrorKind::Bus))
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::enabled(&mut i2c_blocking(Vec::from([Transaction::Read(0x25, &[1])])), Some(true))]
+    #[case::disabled(&mut i2c_blocking(Vec::from([Transaction::Read(0x25, &[0])])), Some(false))]
+    #[case::invalid(&mut i2c_blocking(Vec::from([Transaction::Read(0x25, &[2])])), None)]
+    fn test_is_enabled_blocking(#[case] i2c: &mut I2cTraitMock, #[case] expected: Option<bool>) {
+        let mut device = device_blocking(i2c);
+        match expected {
+            Some(enabled) => assert_eq!(device.is_enabled().unwrap(), enabled),
+            None => assert!(matches!(device.is_enabled(), Err(Error::InvalidData(2)))),
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_is_enabled_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Write(0x25, &[1]),
+            Transaction::Read(0x25, &[1]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        device.enable().await.unwrap();
+        assert!(device.is_enabled().await.unwrap());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::trigger_mode(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x23, &[1]),
+        Transaction::Write(0x24, &[1]),
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+    ])), true)]
+    #[case::continuous_mode(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x23, &[0]),
+    ])), false)]
+    fn test_trigger_and_measure_blocking(#[case] i2c: &mut I2cTraitMock, #[case] triggered: bool) {
+        let mut device = device_blocking(i2c);
+        let result = device.trigger_and_measure(10);
+        if triggered {
+            assert_eq!(result.unwrap().distance, 10);
+        } else {
+            assert!(matches!(result, Err(Error::InvalidState)));
+        }
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_trigger_and_measure_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x23, &[1]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(device.trigger_and_measure(10).await.unwrap().distance, 10);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_trigger_measurement_in_continuous_mode_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x23, &[0]),
+            Transaction::Write(0x23, &[1]),
+            Transaction::Write(0x24, &[1]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_ranging_mode(RangingMode::Continuous).unwrap();
+        assert!(matches!(
+            device.trigger_measurement(),
+            Err(Error::InvalidState)
+        ));
+        device.set_ranging_mode(RangingMode::Trigger).unwrap();
+        device.trigger_measurement().unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_trigger_measurement_after_reading_continuous_mode_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Read(0x23, &[0])]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(device.get_ranging_mode().unwrap(), RangingMode::Continuous);
+        assert!(matches!(
+            device.trigger_measurement(),
+            Err(Error::InvalidState)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_power_mode_invalid_state_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Read(0x28, &[7])]));
+        let mut device = device_blocking(&mut i2c);
+        assert!(matches!(device.get_power_mode(), Err(Error::InvalidState)));
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_trigger_measurement_in_continuous_mode_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Write(0x23, &[0])]));
+        let mut device = device_async(&mut i2c);
+        device
+            .set_ranging_mode(RangingMode::Continuous)
+            .await
+            .unwrap();
+        assert!(matches!(
+            device.trigger_measurement().await,
+            Err(Error::InvalidState)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_distance_and_strength_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Read(0x00, &[10, 0, 0x64, 0])]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(device.get_distance_and_strength().unwrap(), (10, 100));
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_distance_and_strength_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Read(
+            0x00,
+            &[0x2C, 0x01, 0xE8, 0x03],
+        )]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(
+            device.get_distance_and_strength().await.unwrap(),
+            (300, 1000)
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_distance_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([Transaction::Read(0x00, &[10, 0])]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(device.get_distance().unwrap(), 10);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_distance_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Read(0x00, &[10, 0])]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(device.get_distance().await.unwrap(), 10);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::positive(&mut i2c_blocking(Vec::from([Transaction::Read(0x04, &[0xB2, 0x0C])])), 32.5)]
+    #[case::negative(&mut i2c_blocking(Vec::from([Transaction::Read(0x04, &[0x0C, 0xFE])])), -5.0)]
+    fn test_get_temperature_blocking(#[case] i2c: &mut I2cTraitMock, #[case] expected: f32) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.get_temperature().unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_temperature_async() {
+        let mut i2c = i2c_async(Vec::from([Transaction::Read(0x04, &[0xB2, 0x0C])]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(device.get_temperature().await.unwrap(), 32.5);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_measurement_monotonic_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0x00, 0xFF, 0, 0]),
+            // Wrapped around since the previous read
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0x10, 0x00, 0, 0]),
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0x10, 0x00, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(device.get_measurement_monotonic().unwrap().0, 0xFF00);
+        let (timestamp, measurement) = device.get_measurement_monotonic().unwrap();
+        assert_eq!(timestamp, 0x1_0010);
+        assert_eq!(measurement.timestamp, 0x0010);
+        assert_eq!(device.get_measurement_monotonic().unwrap().0, 0x1_0010);
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_measurement_monotonic_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0xFF, 0xFF, 0, 0]),
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0x00, 0x00, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(device.get_measurement_monotonic().await.unwrap().0, 0xFFFF);
+        assert_eq!(
+            device.get_measurement_monotonic().await.unwrap().0,
+            0x1_0000
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_with_delay_blocking() {
+        use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x23, &[0]),
+            Transaction::Read(0x23, &[1]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 0, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_ranging_mode(RangingMode::Continuous).unwrap();
+        let mut delay = CheckedDelay::new(&[DelayTransaction::blocking_delay_ms(10)]);
+        let mut device = device.with_delay(delay.clone());
+        // The known ranging mode is kept
+        assert!(matches!(
+            device.trigger_measurement(),
+            Err(Error::InvalidState)
+        ));
+        assert_eq!(device.trigger_and_measure(10).unwrap().distance, 10);
+        delay.done();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_reset_and_provision_blocking() {
+        use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x29, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::Write(0x25, &[1]),
+        ]));
+        let mut delay = CheckedDelay::new(&[DelayTransaction::blocking_delay_ms(500)]);
+        let mut device = TFLunaBlocking::new(&mut i2c, Address::default(), delay.clone()).unwrap();
+        device.reset_and_provision().unwrap();
+        delay.done();
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_reset_and_provision_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Write(0x29, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::Write(0x25, &[1]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        device.reset_and_provision().await.unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::ultra_low(Vec::from([
+        Transaction::Write(0x1F, &[1]),
+        Transaction::Write(0x20, &[1]),
+        Transaction::Write(0x21, &[2]),
+    ]), PowerMode::UltraLow, &[6, 7, 5])]
+    #[case::power_saving(Vec::from([
+        Transaction::Read(0x00, &[0]),
+        Transaction::Write(0x1F, &[0]),
+        Transaction::Write(0x20, &[1]),
+        Transaction::Write(0x21, &[2]),
+        Transaction::Write(0x28, &[1]),
+    ]), PowerMode::PowerSaving, &[6, 7, 5])]
+    fn test_set_power_mode_with_timing_blocking(
+        #[case] transactions: Vec<Transaction>,
+        #[case] mode: PowerMode,
+        #[case] delays_ms: &[u32],
+    ) {
+        use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+
+        let mut i2c = i2c_blocking(transactions);
+        let expected_delays: Vec<_> = delays_ms
+            .iter()
+            .map(|&ms| DelayTransaction::blocking_delay_ms(ms))
+            .collect();
+        let mut delay = CheckedDelay::new(&expected_delays);
+        let mut device = TFLunaBlocking::new(&mut i2c, Address::default(), delay.clone()).unwrap();
+        let timing = PowerModeTiming {
+            settle_ms: 5,
+            save_ms: 6,
+            reboot_ms: 7,
+        };
+        device.set_power_mode_with_timing(mode, timing).unwrap();
+        delay.done();
+        i2c.done();
+    }
+
+    #[test]
+    fn test_power_mode_timing_default() {
+        assert_eq!(
+            PowerModeTiming::default(),
+            PowerModeTiming {
+                settle_ms: 100,
+                save_ms: 100,
+                reboot_ms: 1000,
+            }
+        );
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::unknown(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[0]),
+    ])), None)]
+    #[case::known_normal(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x28, &[0]),
+    ])), Some(PowerMode::Normal))]
+    #[case::known_power_saving(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x28, &[1]),
+    ])), Some(PowerMode::PowerSaving))]
+    fn test_wake_from_ultra_low_power_known_mode_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] mode: Option<PowerMode>,
+    ) {
+        let mut device = device_blocking(i2c);
+        if let Some(mode) = mode {
+            assert_eq!(device.get_power_mode().unwrap(), mode);
+        }
+        device.wake_from_ultra_low_power().unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_wake_from_ultra_low_power_after_reboot_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x28, &[0]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::Read(0x00, &[0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.get_power_mode().unwrap();
+        device.reboot().unwrap();
+        // The power mode is unknown again
+        device.wake_from_ultra_low_power().unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_wake_from_ultra_low_power_known_mode_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[0]),
+            Transaction::Write(0x1F, &[0]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::Write(0x28, &[0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        device.set_power_mode(PowerMode::Normal).await.unwrap();
+        device.wake_from_ultra_low_power().await.unwrap();
+        i2c.done();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_implemented() {
+        fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+        assert_serde::<SensorReading>();
+        assert_serde::<FirmwareVersion>();
+        assert_serde::<SerialNumber>();
+        assert_serde::<Signature>();
+        assert_serde::<RangingMode>();
+        assert_serde::<PowerMode>();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_averaged_measurement_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 1, 0]),
+            Transaction::Read(0x00, &[103, 0, 0x6E, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+            Transaction::Read(0x00, &[102, 0, 0x64, 0, 0xDE, 0x0D, 3, 0, 2, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(
+            device.get_averaged_measurement(3).unwrap(),
+            SensorReading {
+                distance: 101,
+                signal_strength: 103,
+                temperature: 33.5,
+                timestamp: 3,
+                error: 3,
+            }
+        );
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_get_averaged_measurement_no_samples_blocking() {
+        let mut i2c = i2c_blocking(Vec::new());
+        let mut device = device_blocking(&mut i2c);
+        assert!(matches!(
+            device.get_averaged_measurement(0),
+            Err(Error::InvalidParameter)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_averaged_measurement_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::Read(0x00, &[20, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        let measurement = device.get_averaged_measurement(2).await.unwrap();
+        assert_eq!(measurement.distance, 15);
+        assert_eq!(measurement.timestamp, 2);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::odd(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[100, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+        // Spike
+        Transaction::Read(0x00, &[0xD0, 0x07, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+        Transaction::Read(0x00, &[98, 0, 0x64, 0, 0xB2, 0x0C, 3, 0, 0, 0]),
+        Transaction::Read(0x00, &[0, 0, 0x64, 0, 0xB2, 0x0C, 4, 0, 0, 0]),
+        Transaction::Read(0x00, &[101, 0, 0x64, 0, 0xB2, 0x0C, 5, 0, 0, 0]),
+    ])), 5, 100)]
+    #[case::even(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[20, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+        Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+    ])), 2, 10)]
+    #[case::single(&mut i2c_blocking(Vec::from([
+        Transaction::Read(0x00, &[42, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+    ])), 1, 42)]
+    fn test_get_median_distance_blocking(
+        #[case] i2c: &mut I2cTraitMock,
+        #[case] samples: u8,
+        #[case] expected: u16,
+    ) {
+        let mut device = device_blocking(i2c);
+        assert_eq!(device.get_median_distance(samples).unwrap(), expected);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[rstest]
+    #[case::zero(0)]
+    #[case::above_maximum(10)]
+    fn test_get_median_distance_invalid_samples_blocking(#[case] samples: u8) {
+        let mut i2c = i2c_blocking(Vec::new());
+        let mut device = device_blocking(&mut i2c);
+        assert!(matches!(
+            device.get_median_distance(samples),
+            Err(Error::InvalidParameter)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_median_distance_async() {
+        let mut i2c = i2c_async(Vec::from([
+            Transaction::Read(0x00, &[30, 0, 0x64, 0, 0xB2, 0x0C, 1, 0, 0, 0]),
+            Transaction::Read(0x00, &[10, 0, 0x64, 0, 0xB2, 0x0C, 2, 0, 0, 0]),
+            Transaction::Read(0x00, &[20, 0, 0x64, 0, 0xB2, 0x0C, 3, 0, 0, 0]),
+        ]));
+        let mut device = device_async(&mut i2c);
+        assert_eq!(device.get_median_distance(3).await.unwrap(), 20);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_set_idle_not_unsaved_change_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x23, &[1]),
+            Transaction::Write(0x23, &[0]),
+            Transaction::Write(0x26, &[10, 0]),
+            Transaction::Write(0x23, &[1]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_idle().unwrap();
+        device.resume().unwrap();
+        assert!(!device.has_unsaved_changes());
+        device.set_framerate(10).unwrap();
+        device.set_idle().unwrap();
+        // Earlier changes are still reported
+        assert!(device.has_unsaved_changes());
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_duty_cycle_read_back_to_sleep_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x1F, &[1]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::ReadError(0x00, &[0], NACK),
+            Transaction::Read(0x23, &[1]),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[42, 0, 0x64, 0, 0xB2, 0x0C, 7, 0, 0, 0]),
+            Transaction::ReadError(0x00, &[0], NACK),
+            Transaction::Write(0x24, &[1]),
+            Transaction::Read(0x00, &[43, 0, 0x64, 0, 0xB2, 0x0C, 8, 0, 0, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_power_mode(PowerMode::UltraLow).unwrap();
+        assert_eq!(device.duty_cycle_read().unwrap().distance, 42);
+        assert!(matches!(
+            device.set_framerate(50),
+            Err(Error::UltraLowPowerActive)
+        ));
+        assert_eq!(device.duty_cycle_read().unwrap().distance, 43);
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_duty_cycle_read_error_back_to_sleep_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::Write(0x1F, &[1]),
+            Transaction::Write(0x20, &[1]),
+            Transaction::Write(0x21, &[2]),
+            Transaction::ReadError(0x00, &[0], NACK),
+            Transaction::Read(0x23, &[1]),
+            Transaction::WriteError(0x24, &[1], ErrorKind::Bus),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        device.set_power_mode(PowerMode::UltraLow).unwrap();
+        assert!(matches!(device.duty_cycle_read(), Err(Error::I2c(_))));
+        assert!(matches!(
+            device.set_framerate(50),
+            Err(Error::UltraLowPowerActive)
+        ));
+        i2c.done();
+    }
+
+    #[cfg(feature = "i2c-blocking")]
+    #[test]
+    fn test_read_ultra_low_power_mode_blocks_settings_blocking() {
+        let mut i2c = i2c_blocking(Vec::from([
+            Transaction::ReadError(0x28, &[0], NACK),
+            Transaction::Read(0x3C, b"LUNA"),
+            Transaction::ReadError(0x00, &[0], NACK),
+            Transaction::Write(0x26, &[50, 0]),
+        ]));
+        let mut device = device_blocking(&mut i2c);
+        assert_eq!(device.get_power_mode().unwrap(), PowerMode::UltraLow);
+        assert!(matches!(
+            device.set_framerate(50),
+            Err(Error::UltraLowPowerActive)
+        ));
+        device.wake_from_ultra_low_power().unwrap();
+        device.set_framerate(50).unwrap();
+        i2c.done();
+    }
+}
+
+#[cfg(all(test, feature = "uart"))]
+mod uart_test {
+    extern crate std;
+    use std::collections::VecDeque;
+    use std::vec::Vec;
+
+    use embedded_hal_mock::eh1::delay::StdSleep as Delay;
+    use embedded_io::ErrorKind;
+
+    #[cfg(feature = "async")]
+    use embedded_tfluna::uart::asynchronous::TFLuna as TFLunaAsync;
+    use embedded_tfluna::uart::{
+        Error, blocking::TFLuna as TFLunaBlocking, compute_checksum, verify_frame,
+    };
+    use embedded_tfluna::{FirmwareVersion, SensorReading};
+
+    use rstest::*;
+
+    /// Serial port returning the given bytes and recording the bytes written to it
+    struct SerialMock {
+        rx: VecDeque<u8>,
+        tx: Vec<u8>,
+        expected_tx: Vec<u8>,
+    }
+
+    impl SerialMock {
+        fn new(expected_tx: &[u8], rx: &[u8]) -> Self {
+            Self {
+                rx: rx.iter().copied().collect(),
+                tx: Vec::new(),
+                expected_tx: expected_tx.to_vec(),
+            }
+        }
+
+        fn done(&self) {
+            assert_eq!(self.tx, self.expected_tx);
+            assert!(self.rx.is_empty(), "unread bytes: {:?}", self.rx);
+        }
+
+        fn read_into(&mut self, buf: &mut [u8]) -> usize {
+            let count = buf.len().min(self.rx.len());
+            for (byte, received) in buf.iter_mut().zip(self.rx.drain(..count)) {
+                *byte = received;
+            }
+            count
+        }
+    }
+
+    impl embedded_io::ErrorType for SerialMock {
+        type Error = ErrorKind;
+    }
+
+    impl embedded_io::Read for SerialMock {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            Ok(self.read_into(buf))
+        }
+    }
+
+    impl embedded_io::Write for SerialMock {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.tx.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl embedded_io_async::Read for SerialMock {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            Ok(self.read_into(buf))
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl embedded_io_async::Write for SerialMock {
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.tx.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    const DATA_FRAME: [u8; 9] = [0x59, 0x59, 0x64, 0x00, 0xE8, 0x03, 0xC8, 0x08, 0xD1];
+
+    const READING: SensorReading = SensorReading {
+        distance: 100,
+        signal_strength: 1000,
+        temperature: 25.0,
+        timestamp: 0,
+        error: 0,
+    };
+
+    fn device_blocking(serial: &mut SerialMock) -> TFLunaBlocking<&mut SerialMock, Delay> {
+        TFLunaBlocking::new(serial, Delay {}).unwrap()
+    }
+
+    #[cfg(feature = "async")]
+    fn device_async(serial: &mut SerialMock) -> TFLunaAsync<&mut SerialMock, Delay> {
+        TFLunaAsync::new(serial, Delay {}).unwrap()
+    }
+
+    #[rstest]
+    #[case::aligned(&DATA_FRAME)]
+    #[case::partial_frame_first(&[0xE8, 0x03, 0xC8, 0x08, 0xD1, 0x59, 0x59, 0x64, 0x00, 0xE8, 0x03, 0xC8, 0x08, 0xD1])]
+    fn test_get_measurement_blocking(#[case] rx: &[u8]) {
+        let mut serial = SerialMock::new(&[], rx);
+        let mut device = device_blocking(&mut serial);
+        assert_eq!(device.get_measurement().unwrap(), READING);
+        serial.done();
+    }
+
+    #[test]
+    fn test_get_measurement_wrong_checksum_blocking() {
+        let mut frame = DATA_FRAME;
+        frame[8] += 1;
+        let mut serial = SerialMock::new(&[], &frame);
+        let mut device = device_blocking(&mut serial);
+        assert!(matches!(
+            device.get_measurement(),
+            Err(Error::InvalidData(0xD2))
+        ));
+        serial.done();
+    }
+
+    #[test]
+    fn test_get_measurement_no_data_blocking() {
+        let mut serial = SerialMock::new(&[], &[]);
+        let mut device = device_blocking(&mut serial);
+        assert!(matches!(device.get_measurement(), Err(Error::Timeout)));
+        serial.done();
+    }
+
+    #[test]
+    fn test_enable_skips_data_frames_blocking() {
+        let mut rx = DATA_FRAME.to_vec();
+        rx.extend_from_slice(&[0x5A, 0x05, 0x07, 0x01, 0x67]);
+        let mut serial = SerialMock::new(&[0x5A, 0x05, 0x07, 0x01, 0x67], &rx);
+        let mut device = device_blocking(&mut serial);
+        assert!(device.enable().is_ok());
+        serial.done();
+    }
+
+    #[rstest]
+    #[case::valid(100, &[0x5A, 0x06, 0x03, 0x64, 0x00, 0xC7], true)]
+    #[case::invalid(3, &[], false)]
+    fn test_set_framerate_blocking(#[case] framerate: u16, #[case] frame: &[u8], #[case] ok: bool) {
+        let mut serial = SerialMock::new(frame, frame);
+        let mut device = device_blocking(&mut serial);
+        assert_eq!(device.set_framerate(framerate).is_ok(), ok);
+        serial.done();
+    }
+
+    #[test]
+    fn test_get_firmware_version_blocking() {
+        let mut serial = SerialMock::new(
+            &[0x5A, 0x04, 0x01, 0x5F],
+            &[0x5A, 0x07, 0x01, 0x01, 0x05, 0x03, 0x6B],
+        );
+        let mut device = device_blocking(&mut serial);
+        assert_eq!(
+            device.get_firmware_version().unwrap(),
+            FirmwareVersion {
+                major: 3,
+                minor: 5,
+                revision: 1
+            }
+        );
+        serial.done();
+    }
+
+    #[rstest]
+    #[case::success(&[0x5A, 0x05, 0x11, 0x00, 0x70], true)]
+    #[case::failure(&[0x5A, 0x05, 0x11, 0x01, 0x71], false)]
+    fn test_save_settings_blocking(#[case] response: &[u8], #[case] ok: bool) {
+        let mut serial = SerialMock::new(&[0x5A, 0x04, 0x11, 0x6F], response);
+        let mut device = device_blocking(&mut serial);
+        assert_eq!(device.save_settings().is_ok(), ok);
+        serial.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_measurement_async() {
+        let mut serial = SerialMock::new(&[], &DATA_FRAME);
+        let mut device = device_async(&mut serial);
+        assert_eq!(device.get_measurement().await.unwrap(), READING);
+        serial.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_get_firmware_version_async() {
+        let mut serial = SerialMock::new(
+            &[0x5A, 0x04, 0x01, 0x5F],
+            &[0x5A, 0x07, 0x01, 0x01, 0x05, 0x03, 0x6B],
+        );
+        let mut device = device_async(&mut serial);
+        assert!(device.get_firmware_version().await.is_ok());
+        serial.done();
+    }
+
+    #[rstest]
+    #[case::data_frame(&DATA_FRAME[..8], 0xD1)]
+    #[case::command_frame(&[0x5A, 0x04, 0x01], 0x5F)]
+    #[case::wrapping(&[0xFF, 0xFF, 0x03], 0x01)]
+    #[case::empty(&[], 0x00)]
+    fn test_compute_checksum(#[case] frame: &[u8], #[case] expected: u8) {
+        assert_eq!(compute_checksum(frame), expected);
+    }
+
+    #[test]
+    fn test_verify_frame() {
+        assert!(verify_frame::<ErrorKind>(&DATA_FRAME).is_ok());
+        assert!(verify_frame::<ErrorKind>(&[0x5A, 0x05, 0x11, 0x00, 0x70]).is_ok());
+        assert!(matches!(
+            verify_frame::<ErrorKind>(&[]),
+            Err(Error::InvalidParameter)
+        ));
+    }
+
+    #[rstest]
+    #[case::checksum_plus_one(8, 1)]
+    #[case::checksum_minus_one(8, -1)]
+    #[case::distance_plus_one(2, 1)]
+    #[case::temperature_minus_one(6, -1)]
+    fn test_verify_frame_corrupted(#[case] index: usize, #[case] delta: i8) {
+        let mut frame = DATA_FRAME;
+        frame[index] = frame[index].wrapping_add_signed(delta);
+        assert!(matches!(
+            verify_frame::<ErrorKind>(&frame),
+            Err(Error::InvalidData(checksum)) if checksum == frame[8]
+        ));
+    }
+
+    #[test]
+    fn test_get_measurement_corrupted_distance_blocking() {
+        let mut frame = DATA_FRAME;
+        frame[2] += 1;
+        let mut serial = SerialMock::new(&[], &frame);
+        let mut device = device_blocking(&mut serial);
+        assert!(matches!(
+            device.get_measurement(),
+            Err(Error::InvalidData(0xD1))
+        ));
+        serial.done();
+    }
+
+    #[test]
+    fn test_set_continuous_output_blocking() {
+        let mut serial = SerialMock::new(
+            &[0x5A, 0x05, 0x07, 0x00, 0x66, 0x5A, 0x05, 0x07, 0x01, 0x67],
+            &[
+                0x5A, 0x05, 0x07, 0x00, 0x66, 0x5A, 0x05, 0x07, 0x01, 0x67, 0x59, 0x59, 0x64, 0x00,
+                0xE8, 0x03, 0xC8, 0x08, 0xD1,
+            ],
+        );
+        let mut device = device_blocking(&mut serial);
+        device.set_continuous_output(false).unwrap();
+        // Doesn't wait for a frame
+        assert!(matches!(device.get_measurement(), Err(Error::InvalidState)));
+        device.set_continuous_output(true).unwrap();
+        assert_eq!(device.get_measurement().unwrap(), READING);
+        serial.done();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_set_continuous_output_async() {
+        let mut serial = SerialMock::new(
+            &[0x5A, 0x05, 0x07, 0x00, 0x66],
+            &[0x5A, 0x05, 0x07, 0x00, 0x66],
+        );
+        let mut device = device_async(&mut serial);
+        device.set_continuous_output(false).await.unwrap();
+        assert!(matches!(
+            device.get_measurement().await,
+            Err(Error::InvalidState)
+        ));
+        serial.done();
+    }
+
+    #[rstest]
+    #[case::distance_90cm(&[0x59, 0x59, 0x5A, 0x00, 0xE8, 0x03, 0xC8, 0x08, 0xC7])]
+    #[case::signal_strength(&[0x59, 0x59, 0x64, 0x00, 0x5A, 0x05, 0xC8, 0x08, 0x45])]
+    #[case::other_response(&[0x5A, 0x05, 0x11, 0x00, 0x70])]
+    fn test_enable_skips_frames_with_command_header_blocking(#[case] before: &[u8]) {
+        let mut rx = before.to_vec();
+        rx.extend_from_slice(&[0x5A, 0x05, 0x07, 0x01, 0x67]);
+        let mut serial = SerialMock::new(&[0x5A, 0x05, 0x07, 0x01, 0x67], &rx);
+        let mut device = device_blocking(&mut serial);
+        device.enable().unwrap();
+        serial.done();
+    }
+
+    #[test]
+    fn test_enable_no_response_blocking() {
+        let rx: Vec<u8> = DATA_FRAME.iter().copied().cycle().take(72).collect();
+        let mut serial = SerialMock::new(&[0x5A, 0x05, 0x07, 0x01, 0x67], &rx);
+        let mut device = device_blocking(&mut serial);
+        assert!(matches!(device.enable(), Err(Error::Timeout)));
+    }
+
+    #[rstest]
+    #[case::reboot(&[0x5A, 0x04, 0x02, 0x60], &[0x5A, 0x05, 0x02, 0x00, 0x61])]
+    #[case::restore_factory_defaults(&[0x5A, 0x04, 0x10, 0x6E], &[0x5A, 0x05, 0x10, 0x00, 0x6F])]
+    fn test_output_enabled_again_blocking(#[case] command: &[u8], #[case] response: &[u8]) {
+        let mut tx = Vec::from([0x5A, 0x05, 0x07, 0x00, 0x66]);
+        tx.extend_from_slice(command);
+        let mut rx = Vec::from([0x5A, 0x05, 0x07, 0x00, 0x66]);
+        rx.extend_from_slice(response);
+        rx.extend_from_slice(&DATA_FRAME);
+        let mut serial = SerialMock::new(&tx, &rx);
+        let mut device = device_blocking(&mut serial);
+        device.disable().unwrap();
+        if command[2] == 0x02 {
+            device.reboot().unwrap();
+        } else {
+            device.restore_factory_defaults().unwrap();
+        }
+        assert_eq!(device.get_measurement().unwrap(), READING);
+        serial.done();
+    }
+
+    #[test]
+    fn test_reboot_waits_blocking() {
+        use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+
+        let mut serial =
+            SerialMock::new(&[0x5A, 0x04, 0x02, 0x60], &[0x5A, 0x05, 0x02, 0x00, 0x61]);
+        let mut delay = CheckedDelay::new(&[DelayTransaction::blocking_delay_ms(500)]);
+        let mut device = TFLunaBlocking::new(&mut serial, delay.clone()).unwrap();
+        device.reboot().unwrap();
+        delay.done();
+        serial.done();
+    }
 }